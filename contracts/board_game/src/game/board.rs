@@ -0,0 +1,81 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq,
+)]
+pub enum Tile {
+    Empty,
+    GainCoins(u32),
+    LoseCoins(u32),
+    /// Landing here forces the round to end in a minigame, whatever the wheel says.
+    Minigame,
+    /// Swap positions with another random player.
+    Swap,
+    /// Buy a star if the player can afford it.
+    Shop {
+        star_price: u32,
+    },
+    Star,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct Board {
+    pub tiles: Vec<Tile>,
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        use Tile::*;
+        Self {
+            tiles: vec![
+                Empty,
+                GainCoins(3),
+                LoseCoins(3),
+                GainCoins(3),
+                Minigame,
+                Shop { star_price: 20 },
+                GainCoins(3),
+                LoseCoins(3),
+                Swap,
+                GainCoins(5),
+                LoseCoins(5),
+                Minigame,
+                Star,
+                GainCoins(3),
+                LoseCoins(3),
+                Shop { star_price: 20 },
+                Swap,
+                GainCoins(3),
+                Minigame,
+                LoseCoins(5),
+                GainCoins(5),
+                Empty,
+                LoseCoins(3),
+                Minigame,
+            ],
+        }
+    }
+}
+
+impl Board {
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// Position reached after moving `steps` tiles forward, looping back to the start.
+    pub fn advance(&self, position: usize, steps: usize) -> usize {
+        if self.tiles.is_empty() {
+            return 0;
+        }
+        (position + steps) % self.tiles.len()
+    }
+
+    pub fn tile_at(&self, position: usize) -> Tile {
+        self.tiles.get(position).copied().unwrap_or(Tile::Empty)
+    }
+}
@@ -0,0 +1,207 @@
+use anyhow::{anyhow, Result};
+use borsh::{BorshDeserialize, BorshSerialize};
+use sdk::{ContractName, Identity};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    board::Board,
+    wheel::{Wheel, WheelOutcome},
+};
+
+/// What happens to the coins of a player leaving a game that already started.
+#[derive(
+    Default, Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq,
+)]
+pub enum ForfeitRule {
+    /// Coins go to the jackpot.
+    #[default]
+    Jackpot,
+    /// Coins are split equally between the players still in the game.
+    SplitAmongPlayers,
+}
+
+/// What happens when registration closes without enough players.
+#[derive(
+    Default, Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq,
+)]
+pub enum RegistrationFallback {
+    /// Restart the registration window.
+    #[default]
+    Extend,
+    /// Abort the game and refund the registered players.
+    Abort,
+}
+
+/// Who wins the jackpot, which collects penalties and forfeited coins.
+#[derive(
+    Default, Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq,
+)]
+pub enum JackpotPayout {
+    /// A random player, when the wheel lands on `Jackpot`.
+    #[default]
+    Wheel,
+    /// The player who wins the most coins in the final minigame.
+    FinalMinigameWinner,
+    /// Split equally between the players still in the game when rewards are distributed.
+    SplitAtEnd,
+}
+
+/// How the prize pool is shared between players once the game is over.
+#[derive(
+    Default, Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq,
+)]
+pub enum PayoutPolicy {
+    /// Each player gets a share proportional to their coins.
+    #[default]
+    Proportional,
+    /// The first player gets everything. Tied winners share it.
+    WinnerTakesAll,
+    /// The prize is split between the top ranks, in basis points (e.g. `[6000, 3000, 1000]`).
+    TopSplit(Vec<u16>),
+}
+
+/// Rules of a single game, chosen when the game is initialized.
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq)]
+#[serde(default)]
+pub struct GameConfig {
+    pub rounds: usize,
+    pub min_players: usize,
+    pub max_players: usize,
+    pub registration_fallback: RegistrationFallback,
+    pub registration_window_ms: u64,
+    pub betting_window_ms: u64,
+    /// How long after the start of a round the backend forces the wheel to spin.
+    pub spin_timeout_ms: u64,
+    /// Coins lost by players who did not bet before the betting window closed.
+    pub no_bet_penalty: u64,
+    /// Coins lost by players who committed to a seed but did not reveal it before the first spin.
+    pub unrevealed_seed_penalty: u64,
+    pub max_deposit: u64,
+    pub wheel: Wheel,
+    /// Coins taken from the leader when the wheel lands on `StealFromLeader`.
+    pub steal_amount: u64,
+    pub board: Board,
+    /// SMT token contract players deposit into the game.
+    pub deposit_token: ContractName,
+    /// SMT token contract rewards are paid with. Can be the same as the deposit token.
+    pub reward_token: ContractName,
+    pub leave_forfeit: ForfeitRule,
+    /// Minigames played head to head by two of the bettors, drawn with the dice.
+    pub duel_minigames: Vec<ContractName>,
+    pub jackpot_payout: JackpotPayout,
+    pub payout_policy: PayoutPolicy,
+    /// Share of the prize pool kept by the house, in basis points.
+    pub rake_bps: u16,
+    /// Receives the rake. Defaults to the backend identity.
+    pub treasury: Option<Identity>,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            rounds: 10,
+            min_players: 2,
+            max_players: 20,
+            registration_fallback: RegistrationFallback::default(),
+            registration_window_ms: 55_000,
+            betting_window_ms: 30_000,
+            spin_timeout_ms: 40_000,
+            no_bet_penalty: 10,
+            unrevealed_seed_penalty: 50,
+            max_deposit: 10_000_000,
+            wheel: Wheel::default(),
+            steal_amount: 20,
+            board: Board::default(),
+            deposit_token: ContractName::new("oranj"),
+            reward_token: ContractName::new("oxygen"),
+            leave_forfeit: ForfeitRule::default(),
+            duel_minigames: Vec::new(),
+            jackpot_payout: JackpotPayout::default(),
+            payout_policy: PayoutPolicy::default(),
+            rake_bps: 0,
+            treasury: None,
+        }
+    }
+}
+
+impl GameConfig {
+    pub fn validate(&self) -> Result<()> {
+        if !(1..=50).contains(&self.rounds) {
+            return Err(anyhow!("Rounds must be between 1 and 50"));
+        }
+        if !(1..=100).contains(&self.max_players) {
+            return Err(anyhow!("Max players must be between 1 and 100"));
+        }
+        if self.min_players == 0 || self.min_players > self.max_players {
+            return Err(anyhow!("Min players must be between 1 and max players"));
+        }
+        if !(5_000..=600_000).contains(&self.registration_window_ms) {
+            return Err(anyhow!(
+                "Registration window must be between 5 seconds and 10 minutes"
+            ));
+        }
+        if !(5_000..=300_000).contains(&self.betting_window_ms) {
+            return Err(anyhow!(
+                "Betting window must be between 5 seconds and 5 minutes"
+            ));
+        }
+        if self.spin_timeout_ms < self.betting_window_ms
+            || self.spin_timeout_ms > self.betting_window_ms + 60_000
+        {
+            return Err(anyhow!(
+                "Spin timeout must be at most one minute after the betting window"
+            ));
+        }
+        if !(1..=10_000_000).contains(&self.max_deposit) {
+            return Err(anyhow!("Max deposit must be between 1 and 10000000"));
+        }
+        if self.no_bet_penalty > self.max_deposit {
+            return Err(anyhow!("No-bet penalty cannot exceed the max deposit"));
+        }
+        if self.unrevealed_seed_penalty > self.max_deposit {
+            return Err(anyhow!(
+                "Unrevealed seed penalty cannot exceed the max deposit"
+            ));
+        }
+        if !(1..=16).contains(&self.wheel.len()) {
+            return Err(anyhow!("Wheel must have between 1 and 16 slices"));
+        }
+        if self.wheel.weights().iter().all(|&weight| weight == 0) {
+            return Err(anyhow!("Wheel must have at least one slice with a weight"));
+        }
+        if self.jackpot_payout != JackpotPayout::Wheel
+            && self
+                .wheel
+                .slices
+                .iter()
+                .any(|(outcome, _)| *outcome == WheelOutcome::Jackpot)
+        {
+            return Err(anyhow!(
+                "Jackpot wheel slices require the jackpot to be paid by the wheel"
+            ));
+        }
+        if self.steal_amount > self.max_deposit {
+            return Err(anyhow!("Steal amount cannot exceed the max deposit"));
+        }
+        if !(4..=100).contains(&self.board.len()) {
+            return Err(anyhow!("Board must have between 4 and 100 tiles"));
+        }
+        if self.deposit_token.0.is_empty() || self.reward_token.0.is_empty() {
+            return Err(anyhow!("Token contract names cannot be empty"));
+        }
+        if self.rake_bps > 5_000 {
+            return Err(anyhow!("Rake cannot exceed 50%"));
+        }
+        if let PayoutPolicy::TopSplit(split) = &self.payout_policy {
+            if split.is_empty() || split.len() > self.max_players {
+                return Err(anyhow!(
+                    "Prize split must have between 1 and max players entries"
+                ));
+            }
+            if split.iter().map(|&bps| bps as u32).sum::<u32>() != 10_000 {
+                return Err(anyhow!("Prize split must add up to 10000 basis points"));
+            }
+        }
+        Ok(())
+    }
+}
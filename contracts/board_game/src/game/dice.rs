@@ -1,11 +1,15 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
+/// Deterministic source of randomness for the game, based on xoshiro256**.
+/// Only uses 64-bit integer operations, which keeps it cheap inside the zkVM.
 #[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub struct Dice {
     min: u8,
     max: u8,
+    /// Seed the generator was last seeded with.
     pub seed: u64,
+    state: [u64; 4],
 }
 
 impl Default for Dice {
@@ -14,28 +18,171 @@ impl Default for Dice {
     }
 }
 
+/// Expands a 64-bit seed, as recommended by the xoshiro authors.
+fn splitmix64(x: &mut u64) -> u64 {
+    *x = x.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = *x;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
 impl Dice {
     pub fn new(min: u8, max: u8, seed: u64) -> Self {
         assert!(min < max, "Minimum value must be less than maximum value");
-        Self { min, max, seed }
+        let mut dice = Self {
+            min,
+            max,
+            seed,
+            state: [0; 4],
+        };
+        dice.reseed(seed);
+        dice
+    }
+
+    pub fn reseed(&mut self, seed: u64) {
+        let mut x = seed;
+        self.seed = seed;
+        self.state = [
+            splitmix64(&mut x),
+            splitmix64(&mut x),
+            splitmix64(&mut x),
+            splitmix64(&mut x),
+        ];
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Uniform value in `0..bound`, without modulo bias.
+    fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "Bound must be positive");
+        // Reject the lowest `2^64 % bound` values so every result is equally likely.
+        let threshold = bound.wrapping_neg() % bound;
+        loop {
+            let x = self.next_u64();
+            if x >= threshold {
+                return x % bound;
+            }
+        }
     }
 
     pub fn roll(&mut self) -> u8 {
-        // Simple Linear Congruential Generator
-        self.seed = self.seed.wrapping_mul(1103515245).wrapping_add(12345);
-        let range = (self.max - self.min + 1) as u64;
-        self.min + ((self.seed >> 16) % range) as u8
+        let range = (self.max - self.min) as u64 + 1;
+        self.min + self.below(range) as u8
     }
 
-    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
-        let len = slice.len();
-        if len < 2 {
-            return;
+    /// Uniform index into a slice of length `len`, which must not be empty.
+    pub fn index(&mut self, len: usize) -> usize {
+        self.below(len as u64) as usize
+    }
+
+    /// Picks an index with a probability proportional to its weight.
+    /// Returns None if all weights are zero.
+    pub fn choose_weighted(&mut self, weights: &[u32]) -> Option<usize> {
+        let total: u64 = weights.iter().map(|&w| w as u64).sum();
+        if total == 0 {
+            return None;
         }
-        for i in (1..len).rev() {
-            // Use dice's RNG to pick a random index
-            let j = (self.roll() as usize) % (i + 1);
+        let mut pick = self.below(total);
+        for (i, &weight) in weights.iter().enumerate() {
+            if pick < weight as u64 {
+                return Some(i);
+            }
+            pick -= weight as u64;
+        }
+        None
+    }
+
+    /// Fisher–Yates shuffle, every permutation is equally likely whatever the length.
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            let j = self.index(i + 1);
             slice.swap(i, j);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_rolls() {
+        let mut a = Dice::new(1, 10, 42);
+        let mut b = Dice::new(1, 10, 42);
+        let rolls: Vec<_> = (0..100).map(|_| a.roll()).collect();
+        assert_eq!(rolls, (0..100).map(|_| b.roll()).collect::<Vec<_>>());
+
+        // Reseeding starts the sequence over
+        a.reseed(42);
+        assert_eq!(rolls, (0..100).map(|_| a.roll()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn known_outputs() {
+        // xoshiro256** seeded through splitmix64, from the reference algorithms
+        let mut dice = Dice::default();
+        assert_eq!(dice.next_u64(), 0xbe6a36374160d49b);
+        assert_eq!(dice.next_u64(), 0x214aaa0637a688c6);
+        assert_eq!(dice.next_u64(), 0xf69d16de9954d388);
+    }
+
+    #[test]
+    fn rolls_cover_the_range_evenly() {
+        let mut dice = Dice::new(1, 10, 7);
+        let mut counts = [0u32; 10];
+        for _ in 0..10_000 {
+            let roll = dice.roll();
+            assert!((1..=10).contains(&roll), "Roll {} out of range", roll);
+            counts[(roll - 1) as usize] += 1;
+        }
+        // 1000 expected per face, the margin is over six standard deviations
+        for (face, &count) in counts.iter().enumerate() {
+            assert!(
+                (800..=1200).contains(&count),
+                "Face {} came up {} times",
+                face + 1,
+                count
+            );
+        }
+    }
+
+    #[test]
+    fn full_byte_range_does_not_overflow() {
+        let mut dice = Dice::new(0, u8::MAX, 3);
+        for _ in 0..1_000 {
+            dice.roll();
+        }
+    }
+
+    #[test]
+    fn choose_weighted_skips_zero_weights() {
+        let mut dice = Dice::new(1, 10, 5);
+        assert_eq!(dice.choose_weighted(&[]), None);
+        assert_eq!(dice.choose_weighted(&[0, 0]), None);
+        for _ in 0..100 {
+            assert_eq!(dice.choose_weighted(&[0, 3, 0]), Some(1));
+        }
+    }
+
+    #[test]
+    fn shuffle_is_a_permutation() {
+        let mut dice = Dice::new(1, 10, 11);
+        let mut values: Vec<u32> = (0..50).collect();
+        dice.shuffle(&mut values);
+        let mut sorted = values.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..50).collect::<Vec<_>>());
+    }
+}
@@ -4,24 +4,48 @@ use sdk::{ContractName, Identity, LaneId, StateCommitment};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+use board::Tile;
+pub use config::{ForfeitRule, GameConfig, JackpotPayout, PayoutPolicy, RegistrationFallback};
+use seed::SeedCommitment;
+pub use wheel::WheelOutcome;
+
+pub mod board;
+pub mod config;
 pub mod dice;
 pub mod player;
+pub mod seed;
 pub mod utils;
+pub mod wheel;
 
-const ROUNDS: usize = 10;
-const MAX_PLAYERS: usize = 20;
+/// How many action UUIDs are remembered per player to reject replayed actions.
+const USED_UUIDS_WINDOW: usize = 64;
 
 #[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub struct GameState {
     pub players: Vec<Player>,
-    pub max_players: usize,
+    pub config: GameConfig,
     pub minigames: Vec<ContractName>,
+    pub final_minigame: Option<ContractName>,
+    /// How many times each entry of `minigames` was played this game.
+    pub minigame_plays: Vec<u32>,
+    pub last_minigame: Option<ContractName>,
+    /// Players of the upcoming minigame, drawn when it is picked.
+    pub minigame_setup: MinigameSetup,
     pub dice: dice::Dice,
     pub phase: GamePhase,
     pub round_started_at: u128,
     pub round: usize,
     pub bets: BTreeMap<Identity, u64>,
     pub all_or_nothing: bool,
+    /// Coins not held by any player: lost bets, minigame losses... Paid back when rewards are distributed.
+    pub pot: u64,
+    /// Penalties and forfeited coins, won as configured by `GameConfig::jackpot_payout`.
+    pub jackpot: u64,
+    /// Sum of all deposits of this game. Player coins, the pot and the jackpot always add up to this.
+    pub total_deposits: u64,
+    /// Seed commitments of the players, and optionally the backend, with their secret once
+    /// revealed. Emptied once the revealed secrets are mixed into the dice seed.
+    pub seed_commitments: BTreeMap<Identity, SeedCommitment>,
 
     // Metadata to ensure the game runs smoothly
     pub backend_identity: Identity,
@@ -34,10 +58,48 @@ pub struct Player {
     pub id: Identity,
     pub name: String,
     pub position: usize,
-    pub coins: i32,
+    pub coins: u64,
+    /// What the player deposited, refunded if the game is aborted.
+    pub deposit: u64,
+    pub stars: u32,
     pub used_uuids: Vec<u128>,
 }
 
+impl Player {
+    /// Records the UUID of an action sent by this player, rejecting it if it was already used.
+    /// Only the last `USED_UUIDS_WINDOW` UUIDs are kept so the state stays bounded.
+    fn record_uuid(&mut self, uuid: u128) -> Result<(), ActionError> {
+        if self.used_uuids.contains(&uuid) {
+            return Err(ActionError::DuplicateAction {
+                player_id: self.id.clone(),
+                uuid,
+            });
+        }
+        if self.used_uuids.len() >= USED_UUIDS_WINDOW {
+            self.used_uuids.remove(0);
+        }
+        self.used_uuids.push(uuid);
+        Ok(())
+    }
+}
+
+/// Errors that callers may want to handle specifically, as opposed to generic invalid actions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionError {
+    DuplicateAction { player_id: Identity, uuid: u128 },
+}
+
+impl std::error::Error for ActionError {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq)]
+pub struct Standing {
+    pub player_id: Identity,
+    pub coins: u64,
+    pub stars: u32,
+    /// Starts at 1. Tied players share the same rank, and the next rank is skipped (1, 1, 3).
+    pub rank: usize,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq)]
 pub struct MinigameResult {
     pub contract_name: ContractName,
@@ -59,9 +121,20 @@ pub enum GamePhase {
     InMinigame(ContractName),
     FinalMinigame(ContractName),
     RewardsDistribution,
+    /// The game was aborted, deposits must be sent back before a new game can start.
+    Refunding,
     GameOver,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq)]
+pub enum AbortReason {
+    EndedByBackend,
+    TimedOut,
+    NotEnoughPlayers,
+    /// The backend reveals its seed after the players: withholding it must not pay off.
+    SeedWithheld,
+}
+
 pub type MinigameSetup = Vec<(Identity, String, u64)>;
 
 #[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq)]
@@ -69,16 +142,32 @@ pub enum GameAction {
     EndGame,
     Initialize {
         minigames: Vec<String>,
+        /// Played on the last round. If unset, picked from `minigames` like any other round.
+        #[serde(default)]
+        final_minigame: Option<String>,
+        #[serde(default)]
+        config: GameConfig,
         random_seed: u64,
+        /// Commitment of the backend to its own seed secret, see `seed::commitment`.
+        #[serde(default)]
+        seed_commitment: Option<[u8; 32]>,
     },
     RegisterPlayer {
         name: String,
         deposit: u64, // Initial deposit in coins
+        #[serde(default)]
+        seed_commitment: Option<[u8; 32]>,
+    },
+    /// Reveal the secret behind a seed commitment, once registration is closed.
+    RevealSeed {
+        secret: [u8; 32],
     },
     StartGame,
     PlaceBet {
         amount: u64,
     },
+    /// Leave the game: deposits are refunded during registration, coins are forfeited afterwards.
+    Leave,
     SpinWheel,
     StartMinigame {
         minigame: ContractName,
@@ -89,6 +178,7 @@ pub enum GameAction {
     },
     EndTurn,
     DistributeRewards,
+    RefundDeposits,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
@@ -101,9 +191,17 @@ pub enum GameEvent {
         player_id: Identity,
         new_position: usize,
     },
+    LandedOnTile {
+        player_id: Identity,
+        tile: board::Tile,
+    },
+    StarCollected {
+        player_id: Identity,
+        stars: u32,
+    },
     CoinsChanged {
         player_id: Identity,
-        amount: i32,
+        amount: i64,
     },
     MinigameReady {
         minigame_type: String,
@@ -111,38 +209,77 @@ pub enum GameEvent {
     MinigameStarted {
         minigame_type: String,
     },
+    /// Deltas are the ones applied: gains are capped by what the pot holds.
     MinigameEnded {
         result: MinigameResult,
     },
+    /// No minigame could be played, e.g. only duels are left with a single bettor.
+    MinigameSkipped,
     TurnEnded {
         next_player: Identity,
     },
     GameEnded {
-        winner_id: Identity,
-        final_coins: i32,
+        /// Everyone ranked first: more than one player means a tie.
+        winners: Vec<Identity>,
+        final_coins: u64,
+        standings: Vec<Standing>,
     },
     GameInitialized {
         random_seed: u64,
     },
+    SeedRevealed {
+        player_id: Identity,
+    },
+    SeedFinalized {
+        revealed: Vec<Identity>,
+        missing: Vec<Identity>,
+    },
     PlayerRegistered {
         name: String,
         player_id: Identity,
     },
+    PlayerLeft {
+        player_id: Identity,
+        forfeited: u64,
+    },
     GameStarted {
         player_count: usize,
     },
+    RegistrationExtended {
+        player_count: usize,
+        min_players: usize,
+    },
     BetPlaced {
         player_id: Identity,
         amount: u64,
     },
     WheelSpun {
         round: usize, // for convenience on frontend
-        outcome: u8,
+        outcome: WheelOutcome,
     },
     PlayersSwappedCoins {
         swaps: Vec<(Identity, Identity)>,
     },
+    CoinsStolen {
+        thief: Identity,
+        victim: Identity,
+        amount: u64,
+    },
+    JackpotWon {
+        player_id: Identity,
+        amount: u64,
+    },
+    JackpotChanged {
+        amount: i64,
+        total: u64,
+    },
     AllOrNothingActivated,
+    GameAborted {
+        reason: AbortReason,
+    },
+    DepositsRefunded {
+        total: u64,
+    },
 }
 
 impl From<StateCommitment> for GameState {
@@ -156,13 +293,21 @@ impl GameState {
         Self {
             players: Vec::new(),
             phase: GamePhase::GameOver,
-            max_players: MAX_PLAYERS,
+            config: GameConfig::default(),
             minigames: Vec::new(),
+            final_minigame: None,
+            minigame_plays: Vec::new(),
+            last_minigame: None,
+            minigame_setup: Vec::new(),
             dice: dice::Dice::new(1, 10, 0),
             round_started_at: 0,
             round: 0,
             bets: BTreeMap::new(),
             all_or_nothing: false,
+            pot: 0,
+            jackpot: 0,
+            total_deposits: 0,
+            seed_commitments: BTreeMap::new(),
 
             backend_identity,
             last_interaction_time: 0,
@@ -170,17 +315,31 @@ impl GameState {
         }
     }
 
-    pub fn reset(&mut self, minigames: Vec<ContractName>, random_seed: u64) {
+    pub fn reset(
+        &mut self,
+        minigames: Vec<ContractName>,
+        final_minigame: Option<ContractName>,
+        config: GameConfig,
+        random_seed: u64,
+    ) {
         *self = Self {
-            players: Vec::with_capacity(MAX_PLAYERS),
+            players: Vec::with_capacity(config.max_players),
             phase: GamePhase::GameOver,
-            max_players: MAX_PLAYERS,
+            config,
+            minigame_plays: vec![0; minigames.len()],
             minigames,
+            final_minigame,
+            last_minigame: None,
+            minigame_setup: Vec::new(),
             dice: dice::Dice::new(1, 10, random_seed),
             round_started_at: 0,
             round: 0,
             bets: BTreeMap::new(),
             all_or_nothing: false,
+            pot: 0,
+            jackpot: 0,
+            total_deposits: 0,
+            seed_commitments: BTreeMap::new(),
 
             backend_identity: self.backend_identity.clone(),
             last_interaction_time: self.last_interaction_time,
@@ -188,25 +347,194 @@ impl GameState {
         }
     }
 
-    // Helper function for updating coins and generating events
+    // Helper function for updating coins and generating events.
+    // Coins are taken from and paid to the pot, so a player can't lose more than they have
+    // and can't win more than the pot holds. Returns the signed amount actually moved.
     fn update_player_coins(
         &mut self,
         player_index: usize,
-        delta: i32,
+        delta: i64,
         events: &mut Vec<GameEvent>,
-    ) -> Result<()> {
+    ) -> Result<i64> {
         let Some(player) = self.players.get_mut(player_index) else {
             return Err(anyhow!("Player not found"));
         };
-        player.coins = (player.coins + delta).max(0);
+        let moved = if delta < 0 {
+            let taken = delta.unsigned_abs().min(player.coins);
+            player.coins -= taken;
+            self.pot = self
+                .pot
+                .checked_add(taken)
+                .ok_or_else(|| anyhow!("Pot overflow"))?;
+            -i64::try_from(taken)?
+        } else {
+            let given = (delta as u64).min(self.pot);
+            player.coins = player
+                .coins
+                .checked_add(given)
+                .ok_or_else(|| anyhow!("Coin overflow"))?;
+            self.pot -= given;
+            i64::try_from(given)?
+        };
         events.push(GameEvent::CoinsChanged {
             player_id: player.id.clone(),
-            amount: delta,
+            amount: moved,
         });
+        Ok(moved)
+    }
+
+    /// Takes up to `amount` coins from a player into the jackpot. Returns what was taken.
+    fn penalize_player(
+        &mut self,
+        player_index: usize,
+        amount: u64,
+        events: &mut Vec<GameEvent>,
+    ) -> Result<u64> {
+        let Some(player) = self.players.get_mut(player_index) else {
+            return Err(anyhow!("Player not found"));
+        };
+        let taken = amount.min(player.coins);
+        player.coins -= taken;
+        events.push(GameEvent::CoinsChanged {
+            player_id: player.id.clone(),
+            amount: -i64::try_from(taken)?,
+        });
+        self.jackpot = self
+            .jackpot
+            .checked_add(taken)
+            .ok_or_else(|| anyhow!("Jackpot overflow"))?;
+        events.push(GameEvent::JackpotChanged {
+            amount: i64::try_from(taken)?,
+            total: self.jackpot,
+        });
+        Ok(taken)
+    }
+
+    /// Gives the whole jackpot to a player.
+    fn award_jackpot(&mut self, player_index: usize, events: &mut Vec<GameEvent>) -> Result<()> {
+        if self.jackpot == 0 {
+            return Ok(());
+        }
+        let Some(player) = self.players.get_mut(player_index) else {
+            return Err(anyhow!("Player not found"));
+        };
+        let amount = std::mem::take(&mut self.jackpot);
+        player.coins = player
+            .coins
+            .checked_add(amount)
+            .ok_or_else(|| anyhow!("Coin overflow"))?;
+        events.push(GameEvent::CoinsChanged {
+            player_id: player.id.clone(),
+            amount: i64::try_from(amount)?,
+        });
+        events.push(GameEvent::JackpotWon {
+            player_id: player.id.clone(),
+            amount,
+        });
+        events.push(GameEvent::JackpotChanged {
+            amount: -i64::try_from(amount)?,
+            total: 0,
+        });
+        Ok(())
+    }
+
+    /// Checks that coins were neither created nor destroyed: they are either held by a player,
+    /// in the pot or in the jackpot, and add up to the total deposits.
+    fn check_conservation(&self) -> Result<()> {
+        let mut total = self
+            .pot
+            .checked_add(self.jackpot)
+            .ok_or_else(|| anyhow!("Coin overflow"))?;
+        for player in &self.players {
+            total = total
+                .checked_add(player.coins)
+                .ok_or_else(|| anyhow!("Coin overflow"))?;
+        }
+        if total != self.total_deposits {
+            return Err(anyhow!(
+                "Coin accounting mismatch: {} coins held, {} deposited",
+                total,
+                self.total_deposits
+            ));
+        }
         Ok(())
     }
 
+    /// Transfers of the reward token to make once the game is over, one per recipient.
+    /// The rake goes to the treasury, the jackpot is split if configured so, and the rest
+    /// of the deposits are shared between players following the payout policy.
+    /// Adds up to exactly the total deposits.
+    pub fn reward_transfers(&self) -> Vec<(Identity, u64)> {
+        let mut transfers: BTreeMap<Identity, u64> = BTreeMap::new();
+        let pool = self.total_deposits as u128;
+        let prize = pool - pool * self.config.rake_bps as u128 / 10_000;
+        let standings = self.standings();
+        let jackpot = match self.config.jackpot_payout {
+            JackpotPayout::SplitAtEnd => (self.jackpot as u128).min(prize),
+            _ => 0,
+        };
+        let mut shares = self.prize_shares(&standings, prize - jackpot);
+        // The jackpot is shared equally between players still in the game, if any.
+        let mut jackpot_winners: Vec<_> = (0..standings.len())
+            .filter(|&i| standings[i].coins > 0)
+            .collect();
+        if jackpot_winners.is_empty() {
+            jackpot_winners = (0..standings.len()).collect();
+        }
+        for &i in &jackpot_winners {
+            shares[i] += jackpot / jackpot_winners.len() as u128;
+        }
+        let mut distributed = 0;
+        for (standing, share) in standings.iter().zip(shares) {
+            distributed += share;
+            *transfers.entry(standing.player_id.clone()).or_default() += share as u64;
+        }
+        // Rounding leftovers go to the first ranked player.
+        if let Some(first) = standings.first() {
+            *transfers.entry(first.player_id.clone()).or_default() += (prize - distributed) as u64;
+            distributed = prize;
+        }
+        let treasury = self
+            .config
+            .treasury
+            .clone()
+            .unwrap_or_else(|| self.backend_identity.clone());
+        *transfers.entry(treasury).or_default() += (pool - distributed) as u64;
+        transfers
+            .into_iter()
+            .filter(|(_, amount)| *amount > 0)
+            .collect()
+    }
+
+    /// Share of the prize of each player, in the order of the standings. Rounded down.
+    fn prize_shares(&self, standings: &[Standing], prize: u128) -> Vec<u128> {
+        match &self.config.payout_policy {
+            PayoutPolicy::Proportional => {
+                let everyone_out = standings.iter().all(|s| s.coins == 0);
+                let weight = |s: &Standing| if everyone_out { 1 } else { s.coins as u128 };
+                let total_weight: u128 = standings.iter().map(weight).sum();
+                standings
+                    .iter()
+                    .map(|s| {
+                        if total_weight == 0 {
+                            0
+                        } else {
+                            prize * weight(s) / total_weight
+                        }
+                    })
+                    .collect()
+            }
+            PayoutPolicy::WinnerTakesAll => split_shares(standings, &[10_000], prize),
+            PayoutPolicy::TopSplit(split) => split_shares(standings, split, prize),
+        }
+    }
+
     pub fn get_minigame_setup(&self) -> MinigameSetup {
+        self.minigame_setup.clone()
+    }
+
+    /// Bettors still in the game, who can play the next minigame.
+    fn minigame_candidates(&self) -> MinigameSetup {
         self.bets
             .iter()
             .filter_map(|(id, &bet)| {
@@ -218,41 +546,421 @@ impl GameState {
             .collect()
     }
 
-    // Helper function for handling minigame results
+    // Helper function for handling minigame results.
+    // Returns the delta actually applied, see `update_player_coins`.
     fn apply_minigame_result(
         &mut self,
         player_index: usize,
         result: &PlayerMinigameResult,
         events: &mut Vec<GameEvent>,
-    ) -> Result<()> {
-        if result.coins_delta != 0 {
-            self.update_player_coins(player_index, result.coins_delta, events)?;
+    ) -> Result<i32> {
+        if result.coins_delta == 0 {
+            return Ok(0);
+        }
+        let moved = self.update_player_coins(player_index, result.coins_delta as i64, events)?;
+        Ok(i32::try_from(moved)?)
+    }
+
+    /// Transfers of the deposit token to make when the game is aborted, one per player.
+    /// Players who left after the game started forfeited their deposit: it is shared between
+    /// the others in proportion to their own deposit, like the coins it backed.
+    /// Adds up to exactly the total deposits.
+    pub fn refund_transfers(&self) -> Vec<(Identity, u64)> {
+        let mut transfers: BTreeMap<Identity, u64> = BTreeMap::new();
+        let refunded: u128 = self.players.iter().map(|p| p.deposit as u128).sum();
+        let forfeited = (self.total_deposits as u128).saturating_sub(refunded);
+        let mut shared = 0;
+        for player in &self.players {
+            let share = if refunded == 0 {
+                0
+            } else {
+                forfeited * player.deposit as u128 / refunded
+            };
+            shared += share;
+            *transfers.entry(player.id.clone()).or_default() += player.deposit + share as u64;
+        }
+        // Rounding leftovers go to the first player refunded.
+        if let Some(first) = self.players.iter().find(|p| p.deposit > 0) {
+            *transfers.entry(first.id.clone()).or_default() += (forfeited - shared) as u64;
+        }
+        transfers
+            .into_iter()
+            .filter(|(_, amount)| *amount > 0)
+            .collect()
+    }
+
+    /// Deposit refunded to a player leaving during registration.
+    pub fn leave_refund(&self, caller: &Identity) -> Option<u64> {
+        if self.phase != GamePhase::Registration {
+            return None;
+        }
+        self.players
+            .iter()
+            .find(|p| p.id == *caller)
+            .map(|p| p.deposit)
+    }
+
+    /// Once every player still in the game has bet, moves on to the wheel or the final minigame.
+    fn close_betting_if_complete(&mut self, events: &mut Vec<GameEvent>) -> Result<()> {
+        // Only require bets from players with coins > 0
+        let active_players = self.players.iter().filter(|p| p.coins > 0).count();
+        if self.bets.len() != active_players {
+            return Ok(());
+        }
+        // Give everyone a chance to reveal their seed before the first spin
+        if self.seed_commitments.values().any(|c| c.secret.is_none()) {
+            return Ok(());
+        }
+        if !self.finalize_seed(events)? {
+            return Ok(());
+        }
+        if self.round >= self.config.rounds - 1 {
+            // The final minigame needs players, otherwise it is picked like any other round
+            let final_minigame = match self.final_minigame.clone() {
+                Some(final_minigame) if self.can_play(&final_minigame) => Some(final_minigame),
+                _ => self.pick_minigame(),
+            };
+            let Some(final_minigame) = final_minigame else {
+                events.push(GameEvent::MinigameSkipped);
+                events.push(self.game_ended_event());
+                self.phase = GamePhase::RewardsDistribution;
+                return Ok(());
+            };
+            self.draw_minigame_setup(&final_minigame);
+            events.push(GameEvent::MinigameReady {
+                minigame_type: final_minigame.0.clone(),
+            });
+            self.phase = GamePhase::FinalMinigame(final_minigame);
+        } else {
+            self.phase = GamePhase::WheelSpin;
+        }
+        Ok(())
+    }
+
+    /// Mixes the revealed secrets into the dice seed, once, before the first spin.
+    /// Players who committed without revealing their secret are penalized.
+    /// The backend sees every other secret before revealing its own, so if it doesn't the
+    /// game is aborted and deposits refunded. Returns whether the game goes on.
+    fn finalize_seed(&mut self, events: &mut Vec<GameEvent>) -> Result<bool> {
+        if self.seed_commitments.is_empty() {
+            return Ok(true);
+        }
+        let commitments = std::mem::take(&mut self.seed_commitments);
+        if commitments
+            .get(&self.backend_identity)
+            .is_some_and(|c| c.secret.is_none())
+        {
+            self.abort(AbortReason::SeedWithheld, events);
+            return Ok(false);
+        }
+        self.dice
+            .reseed(seed::combine(self.dice.seed, &commitments));
+        let (revealed, missing): (Vec<_>, Vec<_>) = commitments
+            .iter()
+            .partition(|(_, commitment)| commitment.secret.is_some());
+        let revealed: Vec<Identity> = revealed.into_iter().map(|(id, _)| id.clone()).collect();
+        let missing: Vec<Identity> = missing.into_iter().map(|(id, _)| id.clone()).collect();
+        for id in &missing {
+            if let Some(index) = self.players.iter().position(|p| p.id == *id && p.coins > 0) {
+                self.penalize_player(index, self.config.unrevealed_seed_penalty, events)?;
+            }
+        }
+        events.push(GameEvent::SeedFinalized { revealed, missing });
+        Ok(true)
+    }
+
+    /// Ends the game early: deposits are refunded first if there are any.
+    fn abort(&mut self, reason: AbortReason, events: &mut Vec<GameEvent>) {
+        events.push(GameEvent::GameAborted { reason });
+        if self.refund_transfers().is_empty() {
+            self.reset(
+                self.minigames.clone(),
+                self.final_minigame.clone(),
+                self.config.clone(),
+                self.dice.seed,
+            );
+        } else {
+            self.phase = GamePhase::Refunding;
+        }
+    }
+
+    fn active_player_indices(&self) -> Vec<usize> {
+        (0..self.players.len())
+            .filter(|&i| self.players[i].coins > 0)
+            .collect()
+    }
+
+    /// Pairs players still in the game at random and swaps their coins.
+    /// With an odd number of players, one of them keeps their coins.
+    fn swap_coins(&mut self, events: &mut Vec<GameEvent>) {
+        let mut indices = self.active_player_indices();
+        self.dice.shuffle(&mut indices);
+        let mut swaps = Vec::new();
+        for pair in indices.chunks_exact(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let (coins_a, coins_b) = (self.players[a].coins, self.players[b].coins);
+            self.players[a].coins = coins_b;
+            self.players[b].coins = coins_a;
+            events.push(GameEvent::CoinsChanged {
+                player_id: self.players[a].id.clone(),
+                amount: coins_b as i64 - coins_a as i64,
+            });
+            events.push(GameEvent::CoinsChanged {
+                player_id: self.players[b].id.clone(),
+                amount: coins_a as i64 - coins_b as i64,
+            });
+            swaps.push((self.players[a].id.clone(), self.players[b].id.clone()));
+        }
+        events.push(GameEvent::PlayersSwappedCoins { swaps });
+    }
+
+    /// A random player other than the leader steals up to `steal_amount` coins from them.
+    fn steal_from_leader(&mut self, events: &mut Vec<GameEvent>) -> Result<()> {
+        let active = self.active_player_indices();
+        let Some(&leader) = active.iter().rev().max_by_key(|&&i| self.players[i].coins) else {
+            return Ok(());
+        };
+        let others: Vec<_> = active.into_iter().filter(|&i| i != leader).collect();
+        if others.is_empty() {
+            return Ok(());
         }
+        let thief = others[self.dice.index(others.len())];
+        let stolen =
+            -self.update_player_coins(leader, -i64::try_from(self.config.steal_amount)?, events)?;
+        self.update_player_coins(thief, stolen, events)?;
+        events.push(GameEvent::CoinsStolen {
+            thief: self.players[thief].id.clone(),
+            victim: self.players[leader].id.clone(),
+            amount: stolen as u64,
+        });
         Ok(())
     }
 
+    /// A random player still in the game wins the whole pot.
+    fn pay_jackpot(&mut self, events: &mut Vec<GameEvent>) -> Result<()> {
+        let active = self.active_player_indices();
+        if active.is_empty() || self.jackpot == 0 {
+            return Ok(());
+        }
+        let winner = active[self.dice.index(active.len())];
+        self.award_jackpot(winner, events)
+    }
+
+    /// Checks that an action UUID was not already used by this caller, without recording it.
+    pub fn check_action_uuid(&self, caller: &Identity, uuid: u128) -> Result<(), ActionError> {
+        match self.players.iter().find(|p| p.id == *caller) {
+            Some(player) if player.used_uuids.contains(&uuid) => {
+                Err(ActionError::DuplicateAction {
+                    player_id: caller.clone(),
+                    uuid,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
     fn is_registered(&self, caller: &Identity) -> bool {
         self.players.iter().any(|p| p.id == *caller && p.coins > 0)
     }
 
+    /// Rolls the dice for every player still in the game, moves them along the board
+    /// and applies the effect of the tile they land on.
+    /// Returns true if someone landed on a minigame tile.
+    fn move_players(&mut self, events: &mut Vec<GameEvent>) -> Result<bool> {
+        let mut minigame_triggered = false;
+        let active: Vec<_> = (0..self.players.len())
+            .filter(|&i| self.players[i].coins > 0)
+            .collect();
+        for &i in &active {
+            let value = self.dice.roll();
+            let new_position = self
+                .config
+                .board
+                .advance(self.players[i].position, value as usize);
+            self.players[i].position = new_position;
+            events.push(GameEvent::DiceRolled {
+                player_id: self.players[i].id.clone(),
+                value,
+            });
+            events.push(GameEvent::PlayerMoved {
+                player_id: self.players[i].id.clone(),
+                new_position,
+            });
+
+            let tile = self.config.board.tile_at(new_position);
+            events.push(GameEvent::LandedOnTile {
+                player_id: self.players[i].id.clone(),
+                tile,
+            });
+            match tile {
+                Tile::Empty => {}
+                Tile::GainCoins(amount) => {
+                    self.update_player_coins(i, amount as i64, events)?;
+                }
+                Tile::LoseCoins(amount) => {
+                    self.update_player_coins(i, -(amount as i64), events)?;
+                }
+                Tile::Minigame => {
+                    minigame_triggered = true;
+                }
+                Tile::Swap => {
+                    let others: Vec<_> = active.iter().copied().filter(|&j| j != i).collect();
+                    if others.is_empty() {
+                        continue;
+                    }
+                    let j = others[self.dice.index(others.len())];
+                    let (a, b) = (self.players[i].position, self.players[j].position);
+                    self.players[i].position = b;
+                    self.players[j].position = a;
+                    for k in [i, j] {
+                        events.push(GameEvent::PlayerMoved {
+                            player_id: self.players[k].id.clone(),
+                            new_position: self.players[k].position,
+                        });
+                    }
+                }
+                Tile::Shop { star_price } => {
+                    // Stars are only sold if the player keeps at least one coin, to stay in the game.
+                    if self.players[i].coins > star_price as u64 {
+                        self.update_player_coins(i, -(star_price as i64), events)?;
+                        self.award_star(i, events);
+                    }
+                }
+                Tile::Star => {
+                    self.award_star(i, events);
+                }
+            }
+        }
+        Ok(minigame_triggered)
+    }
+
+    fn award_star(&mut self, player_index: usize, events: &mut Vec<GameEvent>) {
+        let player = &mut self.players[player_index];
+        player.stars += 1;
+        events.push(GameEvent::StarCollected {
+            player_id: player.id.clone(),
+            stars: player.stars,
+        });
+    }
+
+    /// Minigames need a bettor still in the game to play them, duels need two.
+    fn can_play(&self, minigame: &ContractName) -> bool {
+        let needed = if self.config.duel_minigames.contains(minigame) {
+            2
+        } else {
+            1
+        };
+        self.minigame_candidates().len() >= needed
+    }
+
+    /// Picks the minigame for this round. Minigames that were played less are more likely,
+    /// and the same minigame doesn't come up twice in a row unless nothing else can be played.
+    /// Returns `None` when no minigame can be played, e.g. nobody bet this round.
+    fn pick_minigame(&mut self) -> Option<ContractName> {
+        let most_played = self.minigame_plays.iter().copied().max().unwrap_or(0);
+        let weights: Vec<u32> = self
+            .minigames
+            .iter()
+            .zip(&self.minigame_plays)
+            .map(|(minigame, &plays)| {
+                if self.can_play(minigame) {
+                    1 + most_played - plays
+                } else {
+                    0
+                }
+            })
+            .collect();
+        let fresh_weights: Vec<u32> = self
+            .minigames
+            .iter()
+            .zip(&weights)
+            .map(|(minigame, &weight)| {
+                if self.last_minigame.as_ref() == Some(minigame) {
+                    0
+                } else {
+                    weight
+                }
+            })
+            .collect();
+        let index = self
+            .dice
+            .choose_weighted(&fresh_weights)
+            .or_else(|| self.dice.choose_weighted(&weights))?;
+        self.minigame_plays[index] += 1;
+        self.last_minigame = Some(self.minigames[index].clone());
+        Some(self.minigames[index].clone())
+    }
+
+    /// Draws who plays `minigame`: every bettor still in the game, or two of them for a duel.
+    fn draw_minigame_setup(&mut self, minigame: &ContractName) {
+        let mut setup = self.minigame_candidates();
+        if self.config.duel_minigames.contains(minigame) && setup.len() > 2 {
+            self.dice.shuffle(&mut setup);
+            setup.truncate(2);
+            // Back in identity order, like any other setup
+            setup.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+        self.minigame_setup = setup;
+    }
+
+    /// Ranks players by coins, then stars to break ties. Players with the same coins
+    /// and stars are tied and share the same rank.
+    pub fn standings(&self) -> Vec<Standing> {
+        let mut standings: Vec<_> = self
+            .players
+            .iter()
+            .map(|p| Standing {
+                player_id: p.id.clone(),
+                coins: p.coins,
+                stars: p.stars,
+                rank: 0,
+            })
+            .collect();
+        standings.sort_by(|a, b| (b.coins, b.stars).cmp(&(a.coins, a.stars)));
+        let mut previous: Option<(u64, u32, usize)> = None;
+        for (i, standing) in standings.iter_mut().enumerate() {
+            standing.rank = match previous {
+                Some((coins, stars, rank))
+                    if (coins, stars) == (standing.coins, standing.stars) =>
+                {
+                    rank
+                }
+                _ => i + 1,
+            };
+            previous = Some((standing.coins, standing.stars, standing.rank));
+        }
+        standings
+    }
+
+    fn game_ended_event(&self) -> GameEvent {
+        let standings = self.standings();
+        GameEvent::GameEnded {
+            winners: standings
+                .iter()
+                .filter(|s| s.rank == 1)
+                .map(|s| s.player_id.clone())
+                .collect(),
+            final_coins: standings.first().map(|s| s.coins).unwrap_or_default(),
+            standings,
+        }
+    }
+
     /// Checks if the game should end due to players running out of coins.
     /// Emits a GameEnded event and sets phase if needed. Returns true if game ended.
     fn check_and_handle_game_over(&mut self, events: &mut Vec<GameEvent>) -> bool {
         let players_with_coins: Vec<_> = self.players.iter().filter(|p| p.coins > 0).collect();
         if players_with_coins.len() == 1 && self.players.len() > 1 {
-            let winner = players_with_coins[0];
-            events.push(GameEvent::GameEnded {
-                winner_id: winner.id.clone(),
-                final_coins: winner.coins,
-            });
+            events.push(self.game_ended_event());
             self.phase = GamePhase::RewardsDistribution;
             true
         } else if players_with_coins.is_empty() {
-            events.push(GameEvent::GameEnded {
-                winner_id: Identity::default(),
-                final_coins: 0,
-            });
-            self.phase = GamePhase::GameOver;
+            events.push(self.game_ended_event());
+            // Everything ended up in the pot, which still has to be paid back.
+            self.phase = if self.players.is_empty() {
+                GamePhase::GameOver
+            } else {
+                GamePhase::RewardsDistribution
+            };
             true
         } else {
             false
@@ -262,7 +970,22 @@ impl GameState {
     pub fn process_action(
         &mut self,
         caller: &Identity,
-        _uuid: u128,
+        uuid: u128,
+        action: GameAction,
+        timestamp: u128,
+    ) -> Result<Vec<GameEvent>> {
+        if let Some(player) = self.players.iter_mut().find(|p| p.id == *caller) {
+            player.record_uuid(uuid)?;
+        }
+        let events = self.apply_action(caller, uuid, action, timestamp)?;
+        self.check_conservation()?;
+        Ok(events)
+    }
+
+    fn apply_action(
+        &mut self,
+        caller: &Identity,
+        uuid: u128,
         action: GameAction,
         timestamp: u128,
     ) -> Result<Vec<GameEvent>> {
@@ -273,39 +996,87 @@ impl GameState {
                 let is_backend = self.backend_identity == *caller;
                 let backend_timed_out = timestamp - self.last_interaction_time > 2 * 60 * 1000;
                 let game_timed_out = timestamp - self.last_interaction_time > 10 * 60 * 1000;
-                if is_ended || (is_backend && backend_timed_out) || game_timed_out {
+                if !(is_ended || (is_backend && backend_timed_out) || game_timed_out) {
+                    return Err(anyhow!("Only the backend can end the game"));
+                }
+                // If refunds can't go through, give up after a while rather than locking the contract.
+                let give_up = self.phase == GamePhase::Refunding && game_timed_out;
+                if is_ended || give_up || self.refund_transfers().is_empty() {
+                    // Aborted, nobody wins
                     events.push(GameEvent::GameEnded {
-                        winner_id: Identity::default(),
+                        winners: vec![],
                         final_coins: 0,
+                        standings: vec![],
+                    });
+                    self.reset(
+                        self.minigames.clone(),
+                        self.final_minigame.clone(),
+                        self.config.clone(),
+                        self.dice.seed,
+                    );
+                } else if self.phase != GamePhase::Refunding {
+                    // Players get their deposits back before the game can be reset.
+                    events.push(GameEvent::GameAborted {
+                        reason: if game_timed_out {
+                            AbortReason::TimedOut
+                        } else {
+                            AbortReason::EndedByBackend
+                        },
                     });
-                    self.reset(self.minigames.clone(), self.dice.seed);
+                    self.phase = GamePhase::Refunding;
                 } else {
-                    return Err(anyhow!("Only the backend can end the game"));
+                    return Err(anyhow!("Deposits are still being refunded"));
                 }
             }
             (
                 GamePhase::GameOver,
                 GameAction::Initialize {
                     minigames,
+                    final_minigame,
+                    config,
                     random_seed,
+                    seed_commitment,
                 },
             ) => {
+                // The config names the token contracts and who gets the rake
+                if *caller != self.backend_identity {
+                    return Err(anyhow!("Only the backend can initialize a game"));
+                }
                 if minigames.is_empty() {
                     return Err(anyhow!("Minigames cannot be empty"));
                 }
+                config.validate()?;
                 self.reset(
                     minigames.into_iter().map(|x| x.into()).collect::<Vec<_>>(),
+                    final_minigame.map(|x| x.into()),
+                    config,
                     random_seed,
                 );
                 // Keep track of the time to know how long the registration phase lasts.
                 self.round_started_at = timestamp;
                 self.phase = GamePhase::Registration;
+                if let Some(commitment) = seed_commitment {
+                    self.seed_commitments.insert(
+                        self.backend_identity.clone(),
+                        SeedCommitment {
+                            commitment,
+                            secret: None,
+                        },
+                    );
+                }
                 events.push(GameEvent::GameInitialized { random_seed });
             }
 
             // Registration Phase
-            (GamePhase::Registration, GameAction::RegisterPlayer { name, deposit }) => {
-                if self.players.len() >= self.max_players {
+            (
+                GamePhase::Registration,
+                GameAction::RegisterPlayer {
+                    name,
+                    deposit,
+                    seed_commitment,
+                },
+            ) => {
+                if self.players.len() >= self.config.max_players {
                     return Err(anyhow!("Game is full"));
                 }
 
@@ -323,17 +1094,32 @@ impl GameState {
                 if deposit == 0 {
                     return Err(anyhow!("Deposit must be greater than zero"));
                 }
-                if deposit > 10000000 {
+                if deposit > self.config.max_deposit {
                     return Err(anyhow!("Deposit exceeds maximum allowed amount"));
                 }
 
+                self.total_deposits = self
+                    .total_deposits
+                    .checked_add(deposit)
+                    .ok_or_else(|| anyhow!("Deposit overflow"))?;
                 self.players.push(Player {
                     id: caller.clone(),
                     name: name.clone(),
                     position: 0,
-                    coins: deposit as i32,
-                    used_uuids: Vec::new(),
+                    coins: deposit,
+                    deposit,
+                    stars: 0,
+                    used_uuids: vec![uuid],
                 });
+                if let Some(commitment) = seed_commitment {
+                    self.seed_commitments.insert(
+                        caller.clone(),
+                        SeedCommitment {
+                            commitment,
+                            secret: None,
+                        },
+                    );
+                }
 
                 events.push(GameEvent::PlayerRegistered {
                     name: name.clone(),
@@ -343,15 +1129,33 @@ impl GameState {
 
             // Start Game Action
             (GamePhase::Registration, GameAction::StartGame) => {
-                let is_full = self.players.len() == self.max_players;
-                let registration_period_done =
-                    self.round_started_at.saturating_add(55 * 1000) < timestamp;
+                let is_full = self.players.len() == self.config.max_players;
+                let registration_period_done = self
+                    .round_started_at
+                    .saturating_add(self.config.registration_window_ms as u128)
+                    < timestamp;
                 if !is_full && !registration_period_done {
                     return Err(anyhow!(
                         "Game is not full and registration period is not over"
                     ));
                 }
 
+                if self.players.len() < self.config.min_players {
+                    match self.config.registration_fallback {
+                        RegistrationFallback::Extend => {
+                            self.round_started_at = timestamp;
+                            events.push(GameEvent::RegistrationExtended {
+                                player_count: self.players.len(),
+                                min_players: self.config.min_players,
+                            });
+                        }
+                        RegistrationFallback::Abort => {
+                            self.abort(AbortReason::NotEnoughPlayers, &mut events);
+                        }
+                    }
+                    return Ok(events);
+                }
+
                 self.phase = GamePhase::Betting;
                 self.round_started_at = timestamp;
                 self.round = 0;
@@ -362,7 +1166,9 @@ impl GameState {
 
             // Betting Phase
             (GamePhase::Betting, GameAction::PlaceBet { amount }) => {
-                if timestamp.saturating_sub(self.round_started_at) > 30_000 {
+                if timestamp.saturating_sub(self.round_started_at)
+                    > self.config.betting_window_ms as u128
+                {
                     return Err(anyhow!("Betting time is over"));
                 }
                 if self.bets.contains_key(caller) {
@@ -376,10 +1182,10 @@ impl GameState {
                     return Err(anyhow!("Player {} is out of the game (no coins)", caller));
                 }
                 if self.all_or_nothing {
-                    if amount != player.coins as u64 {
+                    if amount != player.coins {
                         return Err(anyhow!("All or nothing round: you must bet all your coins"));
                     }
-                } else if player.coins < amount as i32 {
+                } else if player.coins < amount {
                     return Err(anyhow!("Player {} does not have enough coins", caller));
                 }
                 self.bets.insert(caller.clone(), amount);
@@ -387,23 +1193,82 @@ impl GameState {
                     player_id: caller.clone(),
                     amount,
                 });
-                // Only require bets from players with coins > 0
-                let active_players = self.players.iter().filter(|p| p.coins > 0).count();
-                if self.bets.len() == active_players {
-                    if self.round >= ROUNDS - 1 {
-                        let Some(final_minigame) = self.minigames.first() else {
-                            return Err(anyhow!("No final minigame available"));
-                        };
-                        events.push(GameEvent::MinigameReady {
-                            minigame_type: final_minigame.0.clone(),
-                        });
-                        self.phase = GamePhase::FinalMinigame(final_minigame.clone());
-                    } else {
-                        self.phase = GamePhase::WheelSpin;
+                self.close_betting_if_complete(&mut events)?;
+            }
+
+            // Reveals are only accepted once registration is closed, so that nobody can pick
+            // their commitment knowing someone else's secret.
+            (GamePhase::Betting, GameAction::RevealSeed { secret }) => {
+                let Some(commitment) = self.seed_commitments.get_mut(caller) else {
+                    return Err(anyhow!("No seed commitment to reveal for {}", caller));
+                };
+                if commitment.secret.is_some() {
+                    return Err(anyhow!("Seed already revealed"));
+                }
+                if seed::commitment(&secret, caller) != commitment.commitment {
+                    return Err(anyhow!("Secret does not match the commitment"));
+                }
+                commitment.secret = Some(secret);
+                events.push(GameEvent::SeedRevealed {
+                    player_id: caller.clone(),
+                });
+                self.close_betting_if_complete(&mut events)?;
+            }
+
+            (GamePhase::Registration, GameAction::Leave) => {
+                let Some(index) = self.players.iter().position(|p| p.id == *caller) else {
+                    return Err(anyhow!("Player {} not found", caller));
+                };
+                // The refund transfer is validated in lib.rs
+                let player = self.players.remove(index);
+                self.total_deposits -= player.deposit;
+                self.seed_commitments.remove(caller);
+                events.push(GameEvent::PlayerLeft {
+                    player_id: player.id,
+                    forfeited: 0,
+                });
+            }
+
+            (GamePhase::Betting, GameAction::Leave) => {
+                let Some(index) = self.players.iter().position(|p| p.id == *caller) else {
+                    return Err(anyhow!("Player {} not found", caller));
+                };
+                if self.players[index].coins == 0 {
+                    return Err(anyhow!("Player {} is out of the game (no coins)", caller));
+                }
+                self.bets.remove(caller);
+                // Betting can't wait for a secret that will never be revealed
+                self.seed_commitments.remove(caller);
+                // The deposit backs the coins forfeited to others: if the game is aborted,
+                // it is refunded to them, not to the leaver.
+                self.players[index].deposit = 0;
+                let coins = self.players[index].coins;
+                let forfeited = match self.config.leave_forfeit {
+                    ForfeitRule::Jackpot => self.penalize_player(index, coins, &mut events)? as i64,
+                    ForfeitRule::SplitAmongPlayers => {
+                        -self.update_player_coins(index, -i64::try_from(coins)?, &mut events)?
                     }
-                } else {
-                    self.phase = GamePhase::Betting;
+                };
+                if self.config.leave_forfeit == ForfeitRule::SplitAmongPlayers {
+                    let remaining: Vec<_> = (0..self.players.len())
+                        .filter(|&i| self.players[i].coins > 0)
+                        .collect();
+                    // Rounding leftovers stay in the pot
+                    let share = forfeited / (remaining.len().max(1) as i64);
+                    if share > 0 {
+                        for i in remaining {
+                            self.update_player_coins(i, share, &mut events)?;
+                        }
+                    }
+                }
+                events.push(GameEvent::PlayerLeft {
+                    player_id: caller.clone(),
+                    forfeited: forfeited as u64,
+                });
+                if self.check_and_handle_game_over(&mut events) {
+                    return Ok(events);
                 }
+                self.close_betting_if_complete(&mut events)?;
             }
 
             // Wheel Spin Phase
@@ -411,9 +1276,14 @@ impl GameState {
             | (GamePhase::Betting, GameAction::SpinWheel) => {
                 if self.phase == GamePhase::Betting {
                     // Check we're over the timeout
-                    if timestamp.saturating_sub(self.round_started_at) < 30_000 {
+                    if timestamp.saturating_sub(self.round_started_at)
+                        < self.config.betting_window_ms as u128
+                    {
                         return Err(anyhow!("Not enough time has passed"));
                     }
+                    if !self.finalize_seed(&mut events)? {
+                        return Ok(events);
+                    }
                     // Collect indices of players to penalize
                     let to_penalize: Vec<_> = self
                         .players
@@ -425,13 +1295,14 @@ impl GameState {
                         .map(|(i, _)| i)
                         .collect();
                     for &i in &to_penalize {
-                        if self.round == 0 || self.all_or_nothing {
-                            // In round 0 or all_or_nothing, set coins to 0
-                            self.players[i].coins = 0;
+                        let penalty = if self.round == 0 || self.all_or_nothing {
+                            // In round 0 or all_or_nothing, lose all coins
+                            self.players[i].coins
                         } else {
-                            // Otherwise, penalize 10 coins
-                            self.update_player_coins(i, -10, &mut events)?;
-                        }
+                            // Otherwise, apply the no-bet penalty
+                            self.config.no_bet_penalty
+                        };
+                        self.penalize_player(i, penalty, &mut events)?;
                     }
                 }
                 // Reset after round
@@ -440,27 +1311,32 @@ impl GameState {
                 if self.check_and_handle_game_over(&mut events) {
                     return Ok(events);
                 }
-                // Use dice to determine the wheel outcome
-                let outcome = self.dice.roll() % 5;
+                // Everyone moves on the board before the wheel is spun
+                let minigame_triggered = self.move_players(&mut events)?;
+                if self.check_and_handle_game_over(&mut events) {
+                    return Ok(events);
+                }
+                // Use dice to determine the wheel outcome, unless a minigame tile forces it
+                let outcome = if minigame_triggered {
+                    WheelOutcome::Minigame
+                } else {
+                    let slice = self
+                        .dice
+                        .choose_weighted(&self.config.wheel.weights())
+                        .unwrap_or_default();
+                    self.config.wheel.outcome(slice)
+                };
                 events.push(GameEvent::WheelSpun {
                     outcome,
                     round: self.round,
                 });
                 match outcome {
-                    0 => {
-                        // Nothing happens, go to next round
-                        self.round += 1;
-                        self.bets.clear();
-                        self.round_started_at = timestamp;
-                        self.phase = GamePhase::Betting;
-                    }
-                    1 => {
+                    WheelOutcome::Nothing => {}
+                    WheelOutcome::RandomPayout => {
                         // Randomly pay out the bets to players
                         let bet_entries: Vec<_> =
                             std::mem::take(&mut self.bets).into_iter().collect();
-                        let mut player_indices: Vec<_> = (0..self.players.len())
-                            .filter(|&i| self.players[i].coins > 0)
-                            .collect();
+                        let mut player_indices = self.active_player_indices();
                         self.dice.shuffle(&mut player_indices);
                         for (i, (bettor, amount)) in bet_entries.iter().enumerate() {
                             // Remove bet from bettor
@@ -469,38 +1345,46 @@ impl GameState {
                             else {
                                 return Err(anyhow!("Bettor not found"));
                             };
-                            self.update_player_coins(bettor_idx, -(*amount as i32), &mut events)?;
+                            let lost = self.update_player_coins(
+                                bettor_idx,
+                                -i64::try_from(*amount)?,
+                                &mut events,
+                            )?;
                             // Pay out to a random player
                             let winner_idx = player_indices[i % player_indices.len()];
-                            self.update_player_coins(winner_idx, *amount as i32, &mut events)?;
+                            self.update_player_coins(winner_idx, -lost, &mut events)?;
                         }
-                        self.round += 1;
-                        self.bets.clear();
-                        self.round_started_at = timestamp;
-                        self.phase = GamePhase::Betting;
                     }
-                    2 => {
+                    WheelOutcome::AllOrNothing => {
                         // All or nothing: players must bet all their coins next round
                         self.all_or_nothing = true;
                         events.push(GameEvent::AllOrNothingActivated);
-                        self.round += 1;
-                        self.bets.clear();
-                        self.round_started_at = timestamp;
-                        self.phase = GamePhase::Betting;
                     }
-                    _ => {
+                    WheelOutcome::SwapCoins => self.swap_coins(&mut events),
+                    WheelOutcome::StealFromLeader => self.steal_from_leader(&mut events)?,
+                    WheelOutcome::Jackpot => self.pay_jackpot(&mut events)?,
+                    WheelOutcome::Minigame => {
                         // Minigame: emit MinigameReady and transition to InMinigame for StartMinigame
-                        if let Some(minigame_type) = self.minigames.first() {
+                        if let Some(minigame_type) = self.pick_minigame() {
+                            self.draw_minigame_setup(&minigame_type);
                             events.push(GameEvent::MinigameReady {
                                 minigame_type: minigame_type.0.clone(),
                             });
-                            self.phase = GamePhase::StartMinigame(minigame_type.clone());
-                        } else {
-                            // TODO: should be impossible
-                            return Err(anyhow!("No minigame available"));
+                            self.phase = GamePhase::StartMinigame(minigame_type);
+                            return Ok(events);
                         }
+                        // Nothing can be played, the round goes on as if the wheel landed on Nothing
+                        events.push(GameEvent::MinigameSkipped);
                     }
                 }
+                if self.check_and_handle_game_over(&mut events) {
+                    return Ok(events);
+                }
+                // Go to next round
+                self.round += 1;
+                self.bets.clear();
+                self.round_started_at = timestamp;
+                self.phase = GamePhase::Betting;
             }
 
             (
@@ -541,9 +1425,14 @@ impl GameState {
 
             // InMinigame Phase
             (GamePhase::InMinigame(_), GameAction::EndMinigame { result }) => {
-                // Apply results for each player
-                for player_result in &result.player_results {
-                    self.apply_minigame_result(
+                // Apply results for each player, losses first so the pot can pay for the gains
+                let (losses, gains): (Vec<_>, Vec<_>) = (0..result.player_results.len())
+                    .partition(|&i| result.player_results[i].coins_delta < 0);
+                // What players actually got, the pot may not cover every gain
+                let mut applied = result.clone();
+                for i in losses.into_iter().chain(gains) {
+                    let player_result = &result.player_results[i];
+                    applied.player_results[i].coins_delta = self.apply_minigame_result(
                         self.players
                             .iter()
                             .position(|p| p.id == player_result.player_id)
@@ -553,24 +1442,36 @@ impl GameState {
                     )?;
                 }
 
+                let is_final = self.round >= self.config.rounds - 1;
+                if is_final && self.config.jackpot_payout == JackpotPayout::FinalMinigameWinner {
+                    // First of the biggest winners among players still in the game
+                    let winner = result
+                        .player_results
+                        .iter()
+                        .filter_map(|r| {
+                            let index = self.players.iter().position(|p| p.id == r.player_id)?;
+                            (self.players[index].coins > 0).then_some((index, r.coins_delta))
+                        })
+                        .rev()
+                        .max_by_key(|(_, delta)| *delta);
+                    if let Some((index, _)) = winner {
+                        self.award_jackpot(index, &mut events)?;
+                    }
+                }
+
                 // After coin updates, check for game over
                 if self.check_and_handle_game_over(&mut events) {
                     return Ok(events);
                 }
 
-                events.push(GameEvent::MinigameEnded { result });
+                events.push(GameEvent::MinigameEnded { result: applied });
 
                 // End the game if the round limit is reached
-                if self.round >= ROUNDS - 1 {
-                    let winner = self
-                        .players
-                        .iter()
-                        .max_by_key(|p| p.coins)
-                        .ok_or_else(|| anyhow!("No players found"))?;
-                    events.push(GameEvent::GameEnded {
-                        winner_id: winner.id.clone(),
-                        final_coins: winner.coins,
-                    });
+                if is_final {
+                    if self.players.is_empty() {
+                        return Err(anyhow!("No players found"));
+                    }
+                    events.push(self.game_ended_event());
                     self.phase = GamePhase::RewardsDistribution;
                 } else {
                     self.round += 1;
@@ -585,6 +1486,23 @@ impl GameState {
                 self.phase = GamePhase::GameOver;
             }
 
+            (GamePhase::Refunding, GameAction::RefundDeposits) => {
+                // Refunds are validated in lib.rs
+                events.push(GameEvent::DepositsRefunded {
+                    total: self
+                        .refund_transfers()
+                        .iter()
+                        .map(|(_, amount)| amount)
+                        .sum(),
+                });
+                self.reset(
+                    self.minigames.clone(),
+                    self.final_minigame.clone(),
+                    self.config.clone(),
+                    self.dice.seed,
+                );
+            }
+
             // Invalid phase/action combinations
             (phase, action) => {
                 return Err(anyhow!("Invalid action {:?} for phase {:?}", action, phase));
@@ -594,3 +1512,256 @@ impl GameState {
         Ok(events)
     }
 }
+
+/// Splits the prize between the top positions of the standings, given in basis points.
+/// Tied players share the split of all the positions they occupy, and positions without
+/// a player are left out so the whole prize is given away.
+fn split_shares(standings: &[Standing], split: &[u16], prize: u128) -> Vec<u128> {
+    let mut shares = vec![0; standings.len()];
+    let filled: u128 = split
+        .iter()
+        .take(standings.len())
+        .map(|&bps| bps as u128)
+        .sum();
+    if filled == 0 {
+        return shares;
+    }
+    let mut start = 0;
+    while start < standings.len() {
+        let rank = standings[start].rank;
+        let end = standings[start..]
+            .iter()
+            .position(|s| s.rank != rank)
+            .map_or(standings.len(), |n| start + n);
+        let group: u128 = split
+            .iter()
+            .take(end)
+            .skip(start)
+            .map(|&bps| bps as u128)
+            .sum();
+        shares[start..end].fill(prize * group / filled / (end - start) as u128);
+        start = end;
+    }
+    shares
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Game over with one player per entry of `coins`, who each deposited 100.
+    fn finished_game(coins: &[u64], config: GameConfig) -> GameState {
+        let mut game = GameState::new(Identity::new("backend"));
+        game.config = config;
+        for (i, &coins) in coins.iter().enumerate() {
+            game.players.push(Player {
+                id: Identity::new(format!("player{}", i)),
+                name: format!("Player {}", i),
+                position: 0,
+                coins,
+                deposit: 100,
+                stars: 0,
+                used_uuids: vec![],
+            });
+        }
+        game.total_deposits = 100 * coins.len() as u64;
+        game
+    }
+
+    /// Round 1 of a game whose wheel always lands on a minigame.
+    fn minigame_round(coins: &[u64], minigame: &str, duel: bool) -> GameState {
+        let config = GameConfig {
+            wheel: wheel::Wheel {
+                slices: vec![(WheelOutcome::Minigame, 1)],
+            },
+            duel_minigames: if duel {
+                vec![ContractName::new(minigame)]
+            } else {
+                vec![]
+            },
+            ..GameConfig::default()
+        };
+        let mut game = finished_game(coins, config);
+        game.minigames = vec![ContractName::new(minigame)];
+        game.minigame_plays = vec![0];
+        game.phase = GamePhase::Betting;
+        game.round = 1;
+        game
+    }
+
+    #[test]
+    fn minigames_need_bettors() {
+        let mut game = minigame_round(&[100, 100], "crash_game", false);
+        assert_eq!(game.pick_minigame(), None);
+
+        game.bets.insert(Identity::new("player0"), 10);
+        assert_eq!(game.pick_minigame(), Some(ContractName::new("crash_game")));
+    }
+
+    #[test]
+    fn duels_need_two_bettors() {
+        let mut game = minigame_round(&[100, 100], "reaction_duel", true);
+        game.bets.insert(Identity::new("player0"), 10);
+        assert_eq!(game.pick_minigame(), None);
+
+        game.bets.insert(Identity::new("player1"), 10);
+        assert_eq!(
+            game.pick_minigame(),
+            Some(ContractName::new("reaction_duel"))
+        );
+    }
+
+    #[test]
+    fn minigame_skipped_when_nobody_bet() {
+        let mut game = minigame_round(&[100, 100], "crash_game", false);
+        let timeout = game.config.betting_window_ms as u128 + 1;
+        let backend = game.backend_identity.clone();
+        let events = game
+            .apply_action(&backend, 1, GameAction::SpinWheel, timeout)
+            .unwrap();
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, GameEvent::MinigameSkipped)));
+        // The round goes on instead of waiting for a minigame nobody can play
+        assert_eq!(game.phase, GamePhase::Betting);
+        assert_eq!(game.round, 2);
+    }
+
+    #[test]
+    fn leaving_forfeits_the_deposit() {
+        let mut game = finished_game(&[100, 100, 100], GameConfig::default());
+        game.phase = GamePhase::Betting;
+        let leaver = Identity::new("player0");
+        game.apply_action(&leaver, 1, GameAction::Leave, 0).unwrap();
+        assert_eq!(game.leave_refund(&leaver), None);
+
+        // An abort refunds the leaver's deposit to the players who got their coins
+        let transfers = game.refund_transfers();
+        assert_eq!(transfer_to(&transfers, "player0"), 0);
+        assert_eq!(transfer_to(&transfers, "player1"), 150);
+        assert_eq!(transfer_to(&transfers, "player2"), 150);
+        let total: u64 = transfers.iter().map(|(_, amount)| amount).sum();
+        assert_eq!(total, game.total_deposits);
+    }
+
+    #[test]
+    fn refunds_add_up_to_the_deposits() {
+        let mut game = finished_game(&[100, 100, 100, 100], GameConfig::default());
+        game.players[0].deposit = 0;
+        game.players[2].deposit = 30;
+        game.total_deposits = 330;
+        let transfers = game.refund_transfers();
+        // 100 forfeited, shared 100:30:100, with the rounding leftover to player1
+        assert_eq!(transfer_to(&transfers, "player1"), 100 + 43 + 1);
+        assert_eq!(transfer_to(&transfers, "player2"), 30 + 13);
+        assert_eq!(transfer_to(&transfers, "player3"), 100 + 43);
+        let total: u64 = transfers.iter().map(|(_, amount)| amount).sum();
+        assert_eq!(total, game.total_deposits);
+    }
+
+    fn transfer_to(transfers: &[(Identity, u64)], id: &str) -> u64 {
+        transfers
+            .iter()
+            .find(|(recipient, _)| recipient.0 == id)
+            .map_or(0, |(_, amount)| *amount)
+    }
+
+    fn standing(rank: usize) -> Standing {
+        Standing {
+            player_id: Identity::new(format!("rank{}", rank)),
+            coins: 0,
+            stars: 0,
+            rank,
+        }
+    }
+
+    #[test]
+    fn split_shares_tied_ranks_share_their_places() {
+        let standings = [standing(1), standing(1), standing(3)];
+        assert_eq!(
+            split_shares(&standings, &[6_000, 3_000, 1_000], 1_000),
+            vec![450, 450, 100]
+        );
+    }
+
+    #[test]
+    fn split_shares_unused_places_go_to_the_others() {
+        let standings = [standing(1), standing(2)];
+        assert_eq!(
+            split_shares(&standings, &[6_000, 3_000, 1_000], 900),
+            vec![600, 300]
+        );
+        assert_eq!(split_shares(&standings, &[0, 0], 900), vec![0, 0]);
+    }
+
+    #[test]
+    fn reward_transfers_rake_and_leftover() {
+        let config = GameConfig {
+            rake_bps: 333,
+            treasury: Some(Identity::new("treasury")),
+            ..GameConfig::default()
+        };
+        let transfers = finished_game(&[50, 30, 20], config).reward_transfers();
+        // 9 of the 300 deposits are raked, the rounding leftover goes to the leader
+        assert_eq!(transfer_to(&transfers, "treasury"), 9);
+        assert_eq!(transfer_to(&transfers, "player0"), 146);
+        assert_eq!(transfer_to(&transfers, "player1"), 87);
+        assert_eq!(transfer_to(&transfers, "player2"), 58);
+    }
+
+    #[test]
+    fn reward_transfers_split_jackpot() {
+        let config = GameConfig {
+            jackpot_payout: JackpotPayout::SplitAtEnd,
+            ..GameConfig::default()
+        };
+        let mut game = finished_game(&[60, 0, 20], config);
+        game.jackpot = 20;
+        let transfers = game.reward_transfers();
+        // Only players still in the game share the jackpot
+        assert_eq!(transfer_to(&transfers, "player0"), 220);
+        assert_eq!(transfer_to(&transfers, "player1"), 0);
+        assert_eq!(transfer_to(&transfers, "player2"), 80);
+        assert_eq!(transfer_to(&transfers, "backend"), 0);
+    }
+
+    #[test]
+    fn reward_transfers_add_up_to_the_deposits() {
+        let policies = [
+            PayoutPolicy::Proportional,
+            PayoutPolicy::WinnerTakesAll,
+            PayoutPolicy::TopSplit(vec![5_000, 3_000, 2_000]),
+            PayoutPolicy::TopSplit(vec![7_001, 2_999]),
+        ];
+        let jackpot_payouts = [JackpotPayout::Wheel, JackpotPayout::SplitAtEnd];
+        let games: [&[u64]; 5] = [
+            &[37, 37, 11, 0],
+            &[0, 0, 0],
+            &[1],
+            &[99, 98, 97, 96, 95, 94, 93],
+            &[13, 13, 13],
+        ];
+        for policy in &policies {
+            for jackpot_payout in &jackpot_payouts {
+                for rake_bps in [0, 1, 250, 3_333, 10_000] {
+                    for coins in games {
+                        let config = GameConfig {
+                            payout_policy: policy.clone(),
+                            jackpot_payout: jackpot_payout.clone(),
+                            rake_bps,
+                            ..GameConfig::default()
+                        };
+                        let mut game = finished_game(coins, config);
+                        game.jackpot = 17;
+                        let total: u64 = game.reward_transfers().iter().map(|(_, a)| a).sum();
+                        assert_eq!(
+                            total, game.total_deposits,
+                            "{:?} {:?} rake {} coins {:?}",
+                            policy, jackpot_payout, rake_bps, coins
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
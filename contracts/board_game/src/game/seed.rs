@@ -0,0 +1,38 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use sdk::Identity;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+/// A hash committed at registration, and the secret behind it once revealed.
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq)]
+pub struct SeedCommitment {
+    pub commitment: [u8; 32],
+    pub secret: Option<[u8; 32]>,
+}
+
+/// `sha256(secret || identity)`. Binding the identity stops players from copying
+/// someone else's commitment and reveal.
+pub fn commitment(secret: &[u8; 32], identity: &Identity) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(secret);
+    hasher.update(identity.0.as_bytes());
+    hasher.finalize().into()
+}
+
+/// Mixes all revealed secrets into the initial seed. Nobody controls the result
+/// as long as one participant revealed an unpredictable secret.
+pub fn combine(initial_seed: u64, commitments: &BTreeMap<Identity, SeedCommitment>) -> u64 {
+    let mut hasher = Sha256::new();
+    hasher.update(initial_seed.to_le_bytes());
+    for (identity, commitment) in commitments {
+        if let Some(secret) = &commitment.secret {
+            hasher.update(identity.0.as_bytes());
+            hasher.update(secret);
+        }
+    }
+    let digest: [u8; 32] = hasher.finalize().into();
+    let mut seed = [0; 8];
+    seed.copy_from_slice(&digest[..8]);
+    u64::from_le_bytes(seed)
+}
@@ -1,4 +1,4 @@
-use super::GameEvent;
+use super::{ActionError, GameEvent};
 
 impl std::fmt::Display for GameEvent {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -23,7 +23,24 @@ impl std::fmt::Display for GameEvent {
                 write!(f, "Player {} placed a bet of {}", player_id, amount)
             }
             GameEvent::WheelSpun { round, outcome } => {
-                write!(f, "Wheel spun for round {}, outcome: {}", round, outcome)
+                write!(f, "Wheel spun for round {}, outcome: {:?}", round, outcome)
+            }
+            GameEvent::CoinsStolen {
+                thief,
+                victim,
+                amount,
+            } => {
+                write!(f, "Player {} stole {} coins from {}", thief, amount, victim)
+            }
+            GameEvent::JackpotWon { player_id, amount } => {
+                write!(
+                    f,
+                    "Player {} won the jackpot of {} coins",
+                    player_id, amount
+                )
+            }
+            GameEvent::JackpotChanged { amount, total } => {
+                write!(f, "Jackpot changed by {}, now {} coins", amount, total)
             }
             GameEvent::MinigameReady { minigame_type } => {
                 write!(f, "Minigame '{}' is ready", minigame_type)
@@ -34,22 +51,42 @@ impl std::fmt::Display for GameEvent {
             GameEvent::MinigameEnded { result } => {
                 write!(f, "Minigame ended with result: {:?}", result)
             }
+            GameEvent::MinigameSkipped => {
+                write!(f, "No minigame could be played, skipped")
+            }
             GameEvent::TurnEnded { next_player } => {
                 write!(f, "Turn ended, next player is {}", next_player)
             }
             GameEvent::GameEnded {
-                winner_id,
+                winners,
                 final_coins,
-            } => {
-                write!(
+                ..
+            } => match winners.as_slice() {
+                [] => write!(f, "Game ended without a winner"),
+                [winner] => write!(
                     f,
                     "Game ended, winner is {}, final coins: {}",
-                    winner_id, final_coins
-                )
-            }
+                    winner, final_coins
+                ),
+                _ => write!(
+                    f,
+                    "Game ended in a {}-way tie, final coins: {}",
+                    winners.len(),
+                    final_coins
+                ),
+            },
             GameEvent::GameInitialized { random_seed } => {
                 write!(f, "Game initialized, random seed {}", random_seed)
             }
+            GameEvent::SeedRevealed { player_id } => {
+                write!(f, "Player {} revealed their seed", player_id)
+            }
+            GameEvent::SeedFinalized { revealed, missing } => write!(
+                f,
+                "Seed finalized with {} reveals, {} missing",
+                revealed.len(),
+                missing.len()
+            ),
             GameEvent::PlayerRegistered { name, player_id } => {
                 write!(f, "Player {} registered as {}", name, player_id)
             }
@@ -62,3 +99,13 @@ impl std::fmt::Display for GameEvent {
         }
     }
 }
+
+impl std::fmt::Display for ActionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ActionError::DuplicateAction { player_id, uuid } => {
+                write!(f, "Duplicate action {} from player {}", uuid, player_id)
+            }
+        }
+    }
+}
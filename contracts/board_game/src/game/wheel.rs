@@ -0,0 +1,64 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq,
+)]
+pub enum WheelOutcome {
+    Nothing,
+    /// Bets are taken and paid out to random players.
+    RandomPayout,
+    /// Players must bet all their coins next round.
+    AllOrNothing,
+    Minigame,
+    /// Players are paired at random and swap their coins.
+    SwapCoins,
+    /// A random player steals coins from the leader.
+    StealFromLeader,
+    /// A random player wins the pot.
+    Jackpot,
+}
+
+/// Slices of the wheel, each with its weight: the higher, the more likely.
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq)]
+pub struct Wheel {
+    pub slices: Vec<(WheelOutcome, u32)>,
+}
+
+impl Default for Wheel {
+    fn default() -> Self {
+        use WheelOutcome::*;
+        Self {
+            slices: vec![
+                (Nothing, 2),
+                (RandomPayout, 2),
+                (AllOrNothing, 2),
+                (Minigame, 4),
+                (SwapCoins, 1),
+                (StealFromLeader, 1),
+                (Jackpot, 1),
+            ],
+        }
+    }
+}
+
+impl Wheel {
+    pub fn len(&self) -> usize {
+        self.slices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slices.is_empty()
+    }
+
+    pub fn weights(&self) -> Vec<u32> {
+        self.slices.iter().map(|(_, weight)| *weight).collect()
+    }
+
+    pub fn outcome(&self, slice: usize) -> WheelOutcome {
+        self.slices
+            .get(slice)
+            .map(|(outcome, _)| *outcome)
+            .unwrap_or(WheelOutcome::Nothing)
+    }
+}
@@ -31,7 +31,8 @@ impl ContractAction for GameActionBlob {
     }
 }
 
-fn check_blob_in_calldata(
+/// Checks the transaction also carries `action` on the `contract_name` token contract.
+pub fn check_blob_in_calldata(
     calldata: &Calldata,
     contract_name: ContractName,
     action: SmtTokenAction,
@@ -82,7 +83,7 @@ impl ZkContract for GameState {
             // Ensure player is depositing the correct amount of coins
             check_blob_in_calldata(
                 contract_input,
-                ContractName::new("oranj"),
+                self.config.deposit_token.clone(),
                 SmtTokenAction::Transfer {
                     sender: contract_input.identity.clone(),
                     recipient: Identity::new(exec_ctx.contract_name.clone().0),
@@ -90,15 +91,41 @@ impl ZkContract for GameState {
                 },
             )?;
         } else if let GameAction::DistributeRewards = &action.1 {
-            // Check that we have a transfer blob for all players
-            for player in &self.players {
+            // Check that we have a transfer blob for every payout
+            for (recipient, amount) in self.reward_transfers() {
                 check_blob_in_calldata(
                     contract_input,
-                    ContractName::new("oxygen"),
+                    self.config.reward_token.clone(),
                     SmtTokenAction::Transfer {
                         sender: Identity::new(exec_ctx.contract_name.clone().0),
-                        recipient: player.id.clone(),
-                        amount: player.coins as u128,
+                        recipient,
+                        amount: amount as u128,
+                    },
+                )?;
+            }
+        } else if let GameAction::Leave = &action.1 {
+            // Players leaving during registration get their deposit back
+            if let Some(refund) = self.leave_refund(&contract_input.identity) {
+                check_blob_in_calldata(
+                    contract_input,
+                    self.config.deposit_token.clone(),
+                    SmtTokenAction::Transfer {
+                        sender: Identity::new(exec_ctx.contract_name.clone().0),
+                        recipient: contract_input.identity.clone(),
+                        amount: refund as u128,
+                    },
+                )?;
+            }
+        } else if let GameAction::RefundDeposits = &action.1 {
+            // Check that every player gets their deposit back
+            for (recipient, amount) in self.refund_transfers() {
+                check_blob_in_calldata(
+                    contract_input,
+                    self.config.deposit_token.clone(),
+                    SmtTokenAction::Transfer {
+                        sender: Identity::new(exec_ctx.contract_name.clone().0),
+                        recipient,
+                        amount: amount as u128,
                     },
                 )?;
             }
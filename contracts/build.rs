@@ -21,4 +21,22 @@ fn main() {
             ..Default::default()
         },
     );
+    println!("cargo:rerun-if-changed=sealed_auction/src");
+    build_program_with_args(
+        "./sealed_auction",
+        BuildArgs {
+            features: vec!["sp1".to_string()],
+            output_directory: Some("../elf".to_string()),
+            ..Default::default()
+        },
+    );
+    println!("cargo:rerun-if-changed=reaction_duel/src");
+    build_program_with_args(
+        "./reaction_duel",
+        BuildArgs {
+            features: vec!["sp1".to_string()],
+            output_directory: Some("../elf".to_string()),
+            ..Default::default()
+        },
+    );
 }
@@ -1,6 +1,6 @@
 use anyhow::{anyhow, Result};
 use board_game::game::{MinigameResult, PlayerMinigameResult};
-use board_game::GameActionBlob;
+use board_game::{check_blob_in_calldata, GameActionBlob};
 use borsh::{BorshDeserialize, BorshSerialize};
 use sdk::caller::ExecutionContext;
 use sdk::utils::parse_calldata;
@@ -9,16 +9,23 @@ use sdk::{
     StateCommitment, StructuredBlobData, ZkContract,
 };
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use smt_token::SmtTokenAction;
 use std::collections::BTreeMap;
 
+pub mod multiplier;
 pub mod utils;
 
+pub use multiplier::Multiplier;
+
 #[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub struct Player {
     pub id: Identity,
     pub name: String,
     pub bet: u64,
-    pub cashed_out_at: Option<f64>,
+    pub cashed_out_at: Option<Multiplier>,
+    /// Cash out automatically once the multiplier reaches this target.
+    pub auto_cash_out: Option<Multiplier>,
 }
 
 #[derive(
@@ -35,22 +42,103 @@ pub enum MinigameState {
 #[derive(Default, Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub struct MinigameInstanceVerifiable {
     pub state: MinigameState,
+    /// Standalone round, opened by the backend and joined with token transfers,
+    /// rather than a minigame started by the board.
+    pub standalone: bool,
     pub players: BTreeMap<Identity, Player>,
+    /// Timestamp of the `Start` transaction, cash outs are checked against it.
+    pub started_at: Option<u128>,
+    /// Timestamp of the `Crash` transaction.
+    pub crashed_at: Option<u128>,
+    /// Hash of the crash seed: the head of the seed chain when the game was initialized.
+    pub crash_commitment: [u8; 32],
+    /// Crash seed, revealed when the game crashes. The crash point derives from it.
+    pub crash_seed: Option<[u8; 32]>,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub struct MinigameInstanceBackend {
-    pub current_multiplier: f64,
+    pub current_multiplier: Multiplier,
     pub game_setup_time: Option<u128>,
     pub game_start_time: Option<u128>,
     pub current_time: Option<u128>,
 }
 
+/// Hash chain the crash seeds come from, committed before any of its rounds is played.
+/// Each seed hashes to the one revealed before it, so once bets are known the backend
+/// has no choice left over the seed of the round.
+#[derive(
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    PartialEq,
+    Eq,
+)]
+pub struct SeedChain {
+    /// Chains committed so far, the backend derives each chain from its generation.
+    pub generation: u64,
+    /// Last revealed seed, or the end of the chain before its first round.
+    pub head: [u8; 32],
+    /// Seeds of the chain not revealed yet.
+    pub remaining: u64,
+}
+
+/// Bet sizes and payouts the contract accepts, set by the backend between rounds.
+/// They only bind standalone rounds. Board rounds are exempt: bets can't exceed the coins a
+/// player holds on the board, and the board caps gains at what its pot holds, so the bankroll
+/// never pays for them.
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq,
+)]
+pub struct BetLimits {
+    pub min_bet: u64,
+    pub max_bet: u64,
+    /// Rounds crash at this multiplier at the latest, whatever the crash seed.
+    pub max_multiplier: Multiplier,
+    /// Most the bankroll can lose on a round: winnings paid above the bets collected.
+    /// The round crashes early rather than going over it.
+    pub max_exposure: u64,
+}
+
+impl Default for BetLimits {
+    fn default() -> Self {
+        Self {
+            min_bet: 1,
+            max_bet: 1_000,
+            max_multiplier: MAX_CRASH_POINT,
+            max_exposure: 10_000,
+        }
+    }
+}
+
+impl BetLimits {
+    pub fn check_bet(&self, bet: u64) -> Result<()> {
+        if !(self.min_bet..=self.max_bet).contains(&bet) {
+            return Err(anyhow!(
+                "Invalid bet {}, must be between {} and {}",
+                bet,
+                self.min_bet,
+                self.max_bet
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub struct GameState {
     pub minigame_verifiable: MinigameInstanceVerifiable,
     pub minigame_backend: MinigameInstanceBackend,
     pub board_contract: ContractName,
+    /// SMT token contract standalone rounds are bet and paid in.
+    pub standalone_token: ContractName,
+    pub limits: BetLimits,
+    pub seed_chain: SeedChain,
     pub backend_identity: Identity,
     pub last_interaction_time: u128,
     pub lane_id: LaneId,
@@ -59,21 +147,56 @@ pub struct GameState {
 // Actions that can be performed on-chain
 #[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
 pub enum ChainAction {
+    /// The round commits to the next seed of the `SeedChain`.
+    /// Bets are not checked against `BetLimits`, the board already bounds them.
     InitMinigame {
         players: Vec<(Identity, String, u64)>,
         time: u64,
     },
+    /// Backend only: opens a standalone round, committed like `InitMinigame`.
+    OpenRound {
+        time: u64,
+    },
+    /// Joins a standalone round, along with a transfer of the bet to the contract.
+    Join {
+        name: String,
+        bet: u64,
+    },
+    /// Before the game starts, sets or clears the player's auto cash-out target.
+    SetAutoCashOut {
+        player_id: Identity,
+        target: Option<Multiplier>,
+    },
     Start {
         time: u64,
     },
     CashOut {
         player_id: Identity,
-        multiplier: f64,
+        multiplier: Multiplier,
     },
     Crash {
-        final_multiplier: f64,
+        final_multiplier: Multiplier,
+        /// Must hash to the commitment, and `final_multiplier` must be its `crash_point`.
+        /// It becomes the head of the seed chain.
+        seed: [u8; 32],
     },
     Done,
+    /// Ends a crashed standalone round, along with a transfer of the winnings to each winner.
+    Settle,
+    /// Anyone can end a round the backend stopped moving, see `ABORT_TIMEOUT_MS`.
+    /// The backend can also close a standalone round nobody joined, to make way for the
+    /// board. Comes with the board `EndMinigame`, or the refund transfers of a standalone round.
+    Abort,
+    /// Backend only, between rounds: changes the bet limits.
+    SetLimits {
+        limits: BetLimits,
+    },
+    /// Backend only, once the previous chain is used up: commits the seeds of the next
+    /// `length` rounds. `head` is the last link, the hash of the first seed to be revealed.
+    CommitSeedChain {
+        head: [u8; 32],
+        length: u64,
+    },
 }
 
 // Events that are recorded on-chain
@@ -82,18 +205,44 @@ pub enum ChainEvent {
     MinigameInitialized {
         player_count: usize,
     },
+    RoundOpened,
+    PlayerJoined {
+        player_id: Identity,
+        bet: u64,
+    },
+    AutoCashOutSet {
+        player_id: Identity,
+        target: Option<Multiplier>,
+    },
     GameStarted,
     PlayerCashedOut {
         player_id: Identity,
-        multiplier: f64,
+        multiplier: Multiplier,
         winnings: u64,
     },
     GameCrashed {
-        final_multiplier: f64,
+        final_multiplier: Multiplier,
+    },
+    /// The round crashed before its crash point, at the cap of `BetLimits`.
+    PayoutCapReached {
+        multiplier: Multiplier,
     },
     MinigameEnded {
         final_results: Vec<(Identity, i32)>,
     },
+    RoundSettled {
+        payouts: Vec<(Identity, u64)>,
+    },
+    MinigameAborted {
+        final_results: Vec<(Identity, i32)>,
+    },
+    LimitsUpdated {
+        limits: BetLimits,
+    },
+    SeedChainCommitted {
+        generation: u64,
+        length: u64,
+    },
 }
 
 // Server-side actions for real-time updates
@@ -103,17 +252,19 @@ pub enum ServerAction {
 }
 
 // Server-side events for UI updates
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ServerEvent {
     MultiplierUpdated {
-        multiplier: f64,
+        multiplier: Multiplier,
     },
+    /// A cash out would make the bankroll lose more than `BetLimits::max_exposure`.
     InsufficientFunds {
         player_id: Identity,
         available: u64,
         requested: u64,
     },
     InvalidBetAmount {
+        player_id: Identity,
         min: u64,
         max: u64,
         provided: u64,
@@ -145,6 +296,32 @@ impl ContractAction for ChainActionBlob {
     }
 }
 
+pub const MAX_CRASH_POINT: Multiplier = Multiplier(100 * Multiplier::BPS);
+
+/// How long a round must sit without any transaction before anyone can abort it.
+pub const ABORT_TIMEOUT_MS: u128 = 5 * 60 * 1000;
+
+/// Hash of the crash seed, committed before anyone bets on the round.
+/// It also links the seeds of a `SeedChain`.
+pub fn crash_commitment(seed: &[u8; 32]) -> [u8; 32] {
+    Sha256::digest(seed).into()
+}
+
+/// Multiplier at which the game crashes for this seed: `0.99 / (1 - r)` for a uniform `r`
+/// derived from the seed, so 1% of games crash immediately. Capped at `MAX_CRASH_POINT`.
+pub fn crash_point(seed: &[u8; 32]) -> Multiplier {
+    let mut hasher = Sha256::new();
+    hasher.update(b"crash_point");
+    hasher.update(seed);
+    let digest: [u8; 32] = hasher.finalize().into();
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    // r = h / 2^53, with 53 random bits in h
+    let h = (u64::from_le_bytes(bytes) >> 11) as u128;
+    let bps = (9_900u128 << 53) / ((1u128 << 53) - h);
+    Multiplier(bps.min(MAX_CRASH_POINT.0 as u128) as u64).max(Multiplier::ONE)
+}
+
 impl sdk::FullStateRevert for GameState {}
 
 impl ZkContract for GameState {
@@ -173,10 +350,50 @@ impl ZkContract for GameState {
             return Err("Invalid lane ID".into());
         }
 
+        // Standalone rounds move tokens: check the transfers are part of the transaction
+        if let ChainAction::Join { bet, .. } = &action.1 {
+            check_blob_in_calldata(
+                contract_input,
+                self.standalone_token.clone(),
+                SmtTokenAction::Transfer {
+                    sender: contract_input.identity.clone(),
+                    recipient: Identity::new(exec_ctx.contract_name.0.clone()),
+                    amount: *bet as u128,
+                },
+            )?;
+        } else if let ChainAction::Abort = &action.1 {
+            if self.minigame_verifiable.standalone {
+                for (recipient, amount) in self.refunds() {
+                    check_blob_in_calldata(
+                        contract_input,
+                        self.standalone_token.clone(),
+                        SmtTokenAction::Transfer {
+                            sender: Identity::new(exec_ctx.contract_name.0.clone()),
+                            recipient,
+                            amount: amount as u128,
+                        },
+                    )?;
+                }
+            }
+        } else if let ChainAction::Settle = &action.1 {
+            for (recipient, amount) in self.payouts().map_err(|e| e.to_string())? {
+                check_blob_in_calldata(
+                    contract_input,
+                    self.standalone_token.clone(),
+                    SmtTokenAction::Transfer {
+                        sender: Identity::new(exec_ctx.contract_name.0.clone()),
+                        recipient,
+                        amount: amount as u128,
+                    },
+                )?;
+            }
+        }
+
         let events = self
             .process_chain_action(
                 &contract_input.identity,
                 &action.1,
+                ctx.timestamp.0,
                 Some((&action, &mut exec_ctx)),
             )
             .map_err(|e| e.to_string())?;
@@ -194,11 +411,18 @@ impl ZkContract for GameState {
 }
 
 impl GameState {
-    pub fn new(board_contract: ContractName, backend_identity: Identity) -> Self {
+    pub fn new(
+        board_contract: ContractName,
+        backend_identity: Identity,
+        standalone_token: ContractName,
+    ) -> Self {
         Self {
             minigame_verifiable: MinigameInstanceVerifiable::default(),
             minigame_backend: MinigameInstanceBackend::default(),
             board_contract,
+            standalone_token,
+            limits: BetLimits::default(),
+            seed_chain: SeedChain::default(),
             backend_identity,
             last_interaction_time: 0,
             lane_id: LaneId::default(),
@@ -206,20 +430,30 @@ impl GameState {
     }
 
     // Process on-chain actions that need to be recorded
+    // `timestamp` is the one of the transaction, multipliers are checked against it.
     pub fn process_chain_action(
         &mut self,
         identity: &Identity,
         action: &ChainAction,
+        timestamp: u128,
         ctx: Option<(&ChainActionBlob, &mut ExecutionContext)>,
     ) -> Result<Vec<ChainEvent>> {
         let mut events = Vec::new();
 
         match action {
             ChainAction::InitMinigame { players, .. } => {
-                if self.minigame_verifiable.state != MinigameState::Uninitialized {
+                // The board takes over a standalone round nobody joined
+                let idle_round = self.minigame_verifiable.standalone
+                    && self.minigame_verifiable.state == MinigameState::WaitingForStart
+                    && self.minigame_verifiable.players.is_empty();
+                if self.minigame_verifiable.state != MinigameState::Uninitialized && !idle_round {
                     return Err(anyhow!("Game is already in progress"));
                 }
 
+                if self.seed_chain.remaining == 0 {
+                    return Err(anyhow!("No crash seed left, a new seed chain is needed"));
+                }
+
                 // TODO could just read the other blob directly
                 if let Some((blob, exec_ctx)) = ctx {
                     // Create a new GameActionBlob with the expected data
@@ -239,6 +473,7 @@ impl GameState {
                 }
 
                 let player_count = players.len();
+                self.minigame_verifiable = MinigameInstanceVerifiable::default();
 
                 // Initialize or update player states
                 for (id, name, bet) in players {
@@ -249,16 +484,112 @@ impl GameState {
                             name: name.clone(),
                             bet: *bet,
                             cashed_out_at: None,
+                            auto_cash_out: None,
                         },
                     );
                 }
+                self.minigame_verifiable.crash_commitment = self.seed_chain.head;
 
                 self.minigame_verifiable.state = MinigameState::WaitingForStart;
-                self.minigame_backend.current_multiplier = 1.0;
+                self.minigame_backend.current_multiplier = Multiplier::ONE;
 
                 events.push(ChainEvent::MinigameInitialized { player_count });
             }
 
+            ChainAction::OpenRound { .. } => {
+                if identity != &self.backend_identity {
+                    return Err(anyhow!(
+                        "Only the backend can open a round: {} vs {}",
+                        identity,
+                        self.backend_identity
+                    ));
+                }
+
+                if self.minigame_verifiable.state != MinigameState::Uninitialized {
+                    return Err(anyhow!("Game is already in progress"));
+                }
+
+                if self.seed_chain.remaining == 0 {
+                    return Err(anyhow!("No crash seed left, a new seed chain is needed"));
+                }
+
+                self.minigame_verifiable = MinigameInstanceVerifiable {
+                    state: MinigameState::WaitingForStart,
+                    standalone: true,
+                    crash_commitment: self.seed_chain.head,
+                    ..Default::default()
+                };
+                self.minigame_backend.current_multiplier = Multiplier::ONE;
+
+                events.push(ChainEvent::RoundOpened);
+            }
+
+            ChainAction::Join { name, bet } => {
+                if !self.minigame_verifiable.standalone
+                    || self.minigame_verifiable.state != MinigameState::WaitingForStart
+                {
+                    return Err(anyhow!("No standalone round open to join"));
+                }
+
+                if *bet == 0 {
+                    return Err(anyhow!("Bet must be positive"));
+                }
+                self.limits.check_bet(*bet)?;
+
+                if self.minigame_verifiable.players.contains_key(identity) {
+                    return Err(anyhow!("Player already joined"));
+                }
+
+                self.minigame_verifiable.players.insert(
+                    identity.clone(),
+                    Player {
+                        id: identity.clone(),
+                        name: name.clone(),
+                        bet: *bet,
+                        cashed_out_at: None,
+                        auto_cash_out: None,
+                    },
+                );
+
+                events.push(ChainEvent::PlayerJoined {
+                    player_id: identity.clone(),
+                    bet: *bet,
+                });
+            }
+
+            ChainAction::SetAutoCashOut { player_id, target } => {
+                if self.minigame_verifiable.state != MinigameState::WaitingForStart {
+                    return Err(anyhow!(
+                        "Auto cash-out can only be set before the game starts"
+                    ));
+                }
+
+                if identity != player_id {
+                    return Err(anyhow!("Player ID does not match the action sender"));
+                }
+
+                if let Some(target) = target {
+                    let max_target = self.max_multiplier();
+                    if !(Multiplier::ONE..=max_target).contains(target) {
+                        return Err(anyhow!(
+                            "Invalid auto cash-out target {}, must be between 1x and {}",
+                            target,
+                            max_target
+                        ));
+                    }
+                }
+
+                let Some(player) = self.minigame_verifiable.players.get_mut(player_id) else {
+                    return Err(anyhow!("Player not found"));
+                };
+
+                player.auto_cash_out = *target;
+                events.push(ChainEvent::AutoCashOutSet {
+                    player_id: player_id.clone(),
+                    target: *target,
+                });
+            }
+
             ChainAction::Start { .. } => {
                 if identity != &self.backend_identity {
                     return Err(anyhow!(
@@ -272,8 +603,13 @@ impl GameState {
                     return Err(anyhow!("Game is already in progress"));
                 }
 
+                if self.minigame_verifiable.players.is_empty() {
+                    return Err(anyhow!("Cannot start a game without players"));
+                }
+
                 self.minigame_verifiable.state = MinigameState::Running;
-                self.minigame_backend.current_multiplier = 1.0;
+                self.minigame_verifiable.started_at = Some(timestamp);
+                self.minigame_backend.current_multiplier = Multiplier::ONE;
 
                 events.push(ChainEvent::GameStarted);
             }
@@ -290,6 +626,23 @@ impl GameState {
                     return Err(anyhow!("Player ID does not match the action sender"));
                 }
 
+                // The multiplier can't be more than what the chain time allows
+                let started_at = self
+                    .minigame_verifiable
+                    .started_at
+                    .ok_or_else(|| anyhow!("Game start time is unknown"))?;
+                let max_multiplier =
+                    Self::calculate_multiplier(timestamp.saturating_sub(started_at) as u64);
+                if !(Multiplier::ONE..=max_multiplier).contains(multiplier) {
+                    return Err(anyhow!(
+                        "Invalid cash out multiplier {}, must be between 1x and {}",
+                        multiplier,
+                        max_multiplier
+                    ));
+                }
+
+                // Past the cap, the round is as good as crashed
+                let round_cap = self.round_cap();
                 let Some(player) = self.minigame_verifiable.players.get_mut(player_id) else {
                     return Err(anyhow!("Player not found"));
                 };
@@ -298,20 +651,35 @@ impl GameState {
                     return Err(anyhow!("Bet already cashed out"));
                 }
 
-                player.cashed_out_at = Some(*multiplier);
+                // The auto cash-out kicked in first if the target was reached
+                let multiplier = match player.auto_cash_out {
+                    Some(target) if target <= *multiplier => target,
+                    _ => *multiplier,
+                };
+                if multiplier > round_cap {
+                    return Err(anyhow!(
+                        "Cash out at {} is above the payout cap {}",
+                        multiplier,
+                        round_cap
+                    ));
+                }
+                player.cashed_out_at = Some(multiplier);
 
-                let winnings = Self::calculate_winnings(player.bet, *multiplier);
+                let winnings = Self::calculate_winnings(player.bet, multiplier)?;
                 events.push(ChainEvent::PlayerCashedOut {
                     player_id: player_id.clone(),
-                    multiplier: *multiplier,
+                    multiplier,
                     winnings,
                 });
             }
 
-            ChainAction::Crash { final_multiplier } => {
+            ChainAction::Crash {
+                final_multiplier,
+                seed,
+            } => {
                 if identity != &self.backend_identity {
                     return Err(anyhow!(
-                        "Only the backend can start the game: {} vs {}",
+                        "Only the backend can crash the round: {} vs {}",
                         identity,
                         self.backend_identity
                     ));
@@ -321,9 +689,65 @@ impl GameState {
                     return Err(anyhow!("Game is not running"));
                 }
 
+                if crash_commitment(seed) != self.minigame_verifiable.crash_commitment {
+                    return Err(anyhow!("Crash seed does not match the commitment"));
+                }
+
+                // The round crashes at its crash point, or earlier if it reached the payout cap
+                let expected_multiplier = crash_point(seed).min(self.round_cap());
+                if *final_multiplier != expected_multiplier {
+                    return Err(anyhow!(
+                        "Final multiplier does not match the crash point {}",
+                        expected_multiplier
+                    ));
+                }
+
+                // The backend cannot crash before the multiplier reaches the crash point
+                let started_at = self
+                    .minigame_verifiable
+                    .started_at
+                    .ok_or_else(|| anyhow!("Game start time is unknown"))?;
+                if Self::calculate_multiplier(timestamp.saturating_sub(started_at) as u64)
+                    < *final_multiplier
+                {
+                    return Err(anyhow!("Too early to crash at {}", final_multiplier));
+                }
+
+                // Cash outs above the crash point came in after the crash: those bets are lost,
+                // unless an auto cash-out target was reached before.
+                for player in self.minigame_verifiable.players.values_mut() {
+                    if player.cashed_out_at.is_some_and(|m| m > *final_multiplier) {
+                        player.cashed_out_at = None;
+                    }
+                    if player.cashed_out_at.is_some() {
+                        continue;
+                    }
+                    if let Some(target) = player
+                        .auto_cash_out
+                        .filter(|target| target <= final_multiplier)
+                    {
+                        player.cashed_out_at = Some(target);
+                        events.push(ChainEvent::PlayerCashedOut {
+                            player_id: player.id.clone(),
+                            multiplier: target,
+                            winnings: Self::calculate_winnings(player.bet, target)?,
+                        });
+                    }
+                }
+
                 self.minigame_verifiable.state = MinigameState::Crashed;
+                self.minigame_verifiable.crashed_at = Some(timestamp);
+                self.minigame_verifiable.crash_seed = Some(*seed);
                 self.minigame_backend.current_multiplier = *final_multiplier;
+                // The next round commits to the seed that hashes to this one
+                self.seed_chain.head = *seed;
+                self.seed_chain.remaining -= 1;
 
+                if *final_multiplier < crash_point(seed) {
+                    events.push(ChainEvent::PayoutCapReached {
+                        multiplier: *final_multiplier,
+                    });
+                }
                 events.push(ChainEvent::GameCrashed {
                     final_multiplier: *final_multiplier,
                 });
@@ -333,31 +757,14 @@ impl GameState {
                 if self.minigame_verifiable.state != MinigameState::Crashed {
                     return Err(anyhow!("Cannot end minigame while it is still running"));
                 }
-                let expected_final_results = self.final_results();
+                if self.minigame_verifiable.standalone {
+                    return Err(anyhow!(
+                        "Standalone rounds are settled, not reported to the board"
+                    ));
+                }
+                let expected_final_results = self.final_results()?;
                 if let Some((blob, exec_ctx)) = ctx {
-                    // Create a new GameActionBlob with the expected data
-                    let expected_board_blob = GameActionBlob(
-                        blob.0,
-                        board_game::game::GameAction::EndMinigame {
-                            result: MinigameResult {
-                                contract_name: exec_ctx.contract_name.clone(),
-                                player_results: expected_final_results
-                                    .iter()
-                                    .map(|r| PlayerMinigameResult {
-                                        player_id: r.0.clone(),
-                                        coins_delta: r.1,
-                                    })
-                                    .collect(),
-                            },
-                        },
-                    );
-
-                    // When ending the minigame, verify that the board game is being updated with the correct data
-                    exec_ctx
-                        .is_in_callee_blobs(&self.board_contract, expected_board_blob.clone())
-                        .map_err(|_| {
-                            anyhow!("Missing board game EndMinigame action in transaction, expected: {:?}", expected_board_blob)
-                        })?;
+                    self.check_board_end(blob, exec_ctx, &expected_final_results)?;
                 }
 
                 self.minigame_verifiable = MinigameInstanceVerifiable::default();
@@ -365,6 +772,108 @@ impl GameState {
                     final_results: expected_final_results,
                 });
             }
+
+            ChainAction::Abort => {
+                if !matches!(
+                    self.minigame_verifiable.state,
+                    MinigameState::WaitingForStart | MinigameState::Running
+                ) {
+                    return Err(anyhow!("No round in progress to abort"));
+                }
+
+                // The backend knows the crash point in advance: once players have joined,
+                // closing the round would let it cancel the ones it is about to lose.
+                let closing_standalone = identity == &self.backend_identity
+                    && self.minigame_verifiable.standalone
+                    && self.minigame_verifiable.state == MinigameState::WaitingForStart
+                    && self.minigame_verifiable.players.is_empty();
+                if !closing_standalone
+                    && timestamp.saturating_sub(self.last_interaction_time) < ABORT_TIMEOUT_MS
+                {
+                    return Err(anyhow!("The round is not stalled yet"));
+                }
+
+                // Nobody wins or loses anything, as if the round never happened.
+                // Refund transfers of standalone rounds are checked in `execute`.
+                let final_results = self.aborted_results();
+                if !self.minigame_verifiable.standalone {
+                    if let Some((blob, exec_ctx)) = ctx {
+                        self.check_board_end(blob, exec_ctx, &final_results)?;
+                    }
+                }
+
+                self.minigame_verifiable = MinigameInstanceVerifiable::default();
+                events.push(ChainEvent::MinigameAborted { final_results });
+            }
+
+            ChainAction::Settle => {
+                if self.minigame_verifiable.state != MinigameState::Crashed {
+                    return Err(anyhow!("Cannot settle a round that is still running"));
+                }
+                if !self.minigame_verifiable.standalone {
+                    return Err(anyhow!(
+                        "Board minigames are reported to the board, not settled"
+                    ));
+                }
+                // Transfers are checked in `execute`
+                let payouts = self.payouts()?;
+                self.minigame_verifiable = MinigameInstanceVerifiable::default();
+                events.push(ChainEvent::RoundSettled { payouts });
+            }
+
+            ChainAction::SetLimits { limits } => {
+                if identity != &self.backend_identity {
+                    return Err(anyhow!(
+                        "Only the backend can change the limits: {} vs {}",
+                        identity,
+                        self.backend_identity
+                    ));
+                }
+
+                if self.minigame_verifiable.state != MinigameState::Uninitialized {
+                    return Err(anyhow!("Limits can only change between rounds"));
+                }
+
+                if limits.min_bet > limits.max_bet || limits.max_multiplier < Multiplier::ONE {
+                    return Err(anyhow!("Invalid limits {:?}", limits));
+                }
+
+                self.limits = *limits;
+                events.push(ChainEvent::LimitsUpdated { limits: *limits });
+            }
+
+            ChainAction::CommitSeedChain { head, length } => {
+                if identity != &self.backend_identity {
+                    return Err(anyhow!(
+                        "Only the backend can commit a seed chain: {} vs {}",
+                        identity,
+                        self.backend_identity
+                    ));
+                }
+
+                // Replacing seeds that are still committed would let the backend pick them
+                if self.seed_chain.remaining > 0 {
+                    return Err(anyhow!("The current seed chain is not used up"));
+                }
+
+                if self.minigame_verifiable.state != MinigameState::Uninitialized {
+                    return Err(anyhow!("Seed chains can only change between rounds"));
+                }
+
+                if *length == 0 {
+                    return Err(anyhow!("Seed chain cannot be empty"));
+                }
+
+                self.seed_chain = SeedChain {
+                    generation: self.seed_chain.generation + 1,
+                    head: *head,
+                    remaining: *length,
+                };
+                events.push(ChainEvent::SeedChainCommitted {
+                    generation: self.seed_chain.generation,
+                    length: *length,
+                });
+            }
         }
 
         Ok(events)
@@ -392,23 +901,138 @@ impl GameState {
         Ok(events)
     }
 
-    fn calculate_multiplier(elapsed_millis: u64) -> f64 {
-        let elapsed_secs = elapsed_millis as f64 / 1000.0;
-        (elapsed_secs * 0.2).exp()
+    /// `e^(0.2 * elapsed seconds)`.
+    pub fn calculate_multiplier(elapsed_millis: u64) -> Multiplier {
+        Multiplier::exp(elapsed_millis, 5_000)
+    }
+
+    fn calculate_winnings(bet_amount: u64, multiplier: Multiplier) -> Result<u64> {
+        multiplier.apply(bet_amount)
+    }
+
+    /// What the bankroll loses if the round crashes at `multiplier`, with every player
+    /// still in cashing out at it (or at their auto cash-out target if lower).
+    pub fn exposure_at(&self, multiplier: Multiplier) -> u128 {
+        let (paid, collected) = self.minigame_verifiable.players.values().fold(
+            (0u128, 0u128),
+            |(paid, collected), player| {
+                let cashed_out_at = player
+                    .cashed_out_at
+                    .unwrap_or(multiplier.min(player.auto_cash_out.unwrap_or(multiplier)));
+                let winnings =
+                    player.bet as u128 * cashed_out_at.0 as u128 / Multiplier::BPS as u128;
+                (paid + winnings, collected + player.bet as u128)
+            },
+        );
+        paid.saturating_sub(collected)
     }
 
-    fn calculate_winnings(bet_amount: u64, multiplier: f64) -> u64 {
-        (bet_amount as f64 * multiplier) as u64
+    /// Highest multiplier any round can reach: `BetLimits::max_multiplier` for standalone rounds.
+    fn max_multiplier(&self) -> Multiplier {
+        if self.minigame_verifiable.standalone {
+            self.limits.max_multiplier.min(MAX_CRASH_POINT)
+        } else {
+            MAX_CRASH_POINT
+        }
+    }
+
+    /// Highest multiplier the round can reach. For standalone rounds, it is lowered so that
+    /// the bankroll never loses more than `BetLimits::max_exposure`, and only goes up as
+    /// players cash out. Board rounds have no bankroll at stake.
+    pub fn round_cap(&self) -> Multiplier {
+        if !self.minigame_verifiable.standalone {
+            return self.max_multiplier();
+        }
+        let max_exposure = self.limits.max_exposure as u128;
+        let (mut low, mut high) = (Multiplier::ONE.0, self.max_multiplier().0);
+        if self.exposure_at(Multiplier(high)) <= max_exposure {
+            return Multiplier(high);
+        }
+        // Exposure grows with the multiplier: find the last one within the limit
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if self.exposure_at(Multiplier(mid)) <= max_exposure {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        Multiplier(low)
     }
 
     pub fn get_end_results(&self) -> Result<Vec<(Identity, i32)>> {
         if self.minigame_verifiable.state != MinigameState::Crashed {
             return Err(anyhow!("Game is still running"));
         }
-        Ok(self.final_results())
+        self.final_results()
+    }
+
+    /// Tokens owed to the winners of a standalone round: their bet times their multiplier.
+    pub fn payouts(&self) -> Result<Vec<(Identity, u64)>> {
+        let mut payouts = Vec::new();
+        for player in self.minigame_verifiable.players.values() {
+            if let Some(multiplier) = player.cashed_out_at {
+                let winnings = Self::calculate_winnings(player.bet, multiplier)?;
+                if winnings > 0 {
+                    payouts.push((player.id.clone(), winnings));
+                }
+            }
+        }
+        Ok(payouts)
+    }
+
+    /// Bets to send back when a standalone round is aborted.
+    pub fn refunds(&self) -> Vec<(Identity, u64)> {
+        self.minigame_verifiable
+            .players
+            .values()
+            .map(|player| (player.id.clone(), player.bet))
+            .collect()
+    }
+
+    /// Results of an aborted round: every player keeps their coins.
+    pub fn aborted_results(&self) -> Vec<(Identity, i32)> {
+        self.minigame_verifiable
+            .players
+            .keys()
+            .map(|id| (id.clone(), 0))
+            .collect()
+    }
+
+    /// When ending the minigame, verify that the board game is being updated with the correct data
+    fn check_board_end(
+        &self,
+        blob: &ChainActionBlob,
+        exec_ctx: &mut ExecutionContext,
+        final_results: &[(Identity, i32)],
+    ) -> Result<()> {
+        let expected_board_blob = GameActionBlob(
+            blob.0,
+            board_game::game::GameAction::EndMinigame {
+                result: MinigameResult {
+                    contract_name: exec_ctx.contract_name.clone(),
+                    player_results: final_results
+                        .iter()
+                        .map(|r| PlayerMinigameResult {
+                            player_id: r.0.clone(),
+                            coins_delta: r.1,
+                        })
+                        .collect(),
+                },
+            },
+        );
+
+        exec_ctx
+            .is_in_callee_blobs(&self.board_contract, expected_board_blob.clone())
+            .map_err(|_| {
+                anyhow!(
+                    "Missing board game EndMinigame action in transaction, expected: {:?}",
+                    expected_board_blob
+                )
+            })
     }
 
-    pub fn final_results(&self) -> Vec<(Identity, i32)> {
+    pub fn final_results(&self) -> Result<Vec<(Identity, i32)>> {
         self.minigame_verifiable
             .players
             .iter()
@@ -419,17 +1043,174 @@ impl GameState {
                         bet, cashed_out_at, ..
                     },
                 )| {
-                    let bet = *bet as f64;
                     let delta = if let Some(multiplier) = cashed_out_at {
                         // Player cashed out - calculate profit
-                        (bet * multiplier - bet) as i32
+                        Self::calculate_winnings(*bet, *multiplier)? as i128 - *bet as i128
                     } else {
                         // Player didn't cash out - lost their bet
-                        -bet as i32
+                        -(*bet as i128)
                     };
-                    (id.clone(), delta)
+                    let delta = i32::try_from(delta)
+                        .map_err(|_| anyhow!("Result for {} does not fit in a coin delta", id))?;
+                    Ok((id.clone(), delta))
                 },
             )
-            .collect::<Vec<_>>()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crash_point_known_values() {
+        assert_eq!(crash_point(&[0; 32]), Multiplier(18_208));
+        assert_eq!(crash_point(&[1; 32]), Multiplier(12_529));
+        assert_eq!(crash_point(&[0xff; 32]), Multiplier(20_031));
+    }
+
+    #[test]
+    fn crash_point_within_bounds() {
+        let mut seed = [0; 32];
+        let mut instant_crashes = 0;
+        for _ in 0..10_000 {
+            seed = crash_commitment(&seed);
+            let point = crash_point(&seed);
+            assert!((Multiplier::ONE..=MAX_CRASH_POINT).contains(&point));
+            if point == Multiplier::ONE {
+                instant_crashes += 1;
+            }
+        }
+        // About 1% of rounds crash right away
+        assert!((50..=150).contains(&instant_crashes), "{}", instant_crashes);
+    }
+
+    #[test]
+    fn multiplier_over_time() {
+        assert_eq!(GameState::calculate_multiplier(0), Multiplier::ONE);
+        assert_eq!(GameState::calculate_multiplier(5_000), Multiplier(27_182));
+        // The multiplier passes the highest crash point after about 23 seconds
+        assert!(GameState::calculate_multiplier(23_000) < MAX_CRASH_POINT);
+        assert!(GameState::calculate_multiplier(23_100) > MAX_CRASH_POINT);
+        assert_eq!(GameState::calculate_multiplier(u64::MAX), Multiplier::MAX);
+    }
+
+    /// Round waiting to start with one player per bet.
+    fn round(standalone: bool, bets: &[u64], limits: BetLimits) -> GameState {
+        let mut state = GameState::new(
+            ContractName::new("board_game"),
+            Identity::new("backend"),
+            ContractName::new("oranj"),
+        );
+        state.limits = limits;
+        state.minigame_verifiable.state = MinigameState::WaitingForStart;
+        state.minigame_verifiable.standalone = standalone;
+        for (i, &bet) in bets.iter().enumerate() {
+            let id = Identity::new(format!("player{}", i));
+            state.minigame_verifiable.players.insert(
+                id.clone(),
+                Player {
+                    id,
+                    name: format!("Player {}", i),
+                    bet,
+                    cashed_out_at: None,
+                    auto_cash_out: None,
+                },
+            );
+        }
+        state
+    }
+
+    #[test]
+    fn backend_only_closes_empty_standalone_rounds() {
+        let backend = Identity::new("backend");
+        let mut state = round(true, &[], BetLimits::default());
+        assert!(state
+            .process_chain_action(&backend, &ChainAction::Abort, 1_000, None)
+            .is_ok());
+
+        // Once someone joined, the round plays out or waits for the timeout like any other
+        let mut state = round(true, &[100], BetLimits::default());
+        assert!(state
+            .process_chain_action(&backend, &ChainAction::Abort, 1_000, None)
+            .is_err());
+        assert!(state
+            .process_chain_action(&backend, &ChainAction::Abort, ABORT_TIMEOUT_MS, None)
+            .is_ok());
+    }
+
+    fn exposure_limits(max_exposure: u64) -> BetLimits {
+        BetLimits {
+            max_exposure,
+            ..BetLimits::default()
+        }
+    }
+
+    #[test]
+    fn round_cap_ignores_limits_on_board_rounds() {
+        let limits = BetLimits {
+            max_multiplier: Multiplier(20_000),
+            max_exposure: 0,
+            ..BetLimits::default()
+        };
+        assert_eq!(round(false, &[1_000], limits).round_cap(), MAX_CRASH_POINT);
+    }
+
+    #[test]
+    fn round_cap_within_limits() {
+        let state = round(true, &[10], BetLimits::default());
+        assert_eq!(state.round_cap(), MAX_CRASH_POINT);
+
+        let limits = BetLimits {
+            max_multiplier: Multiplier(50_000),
+            ..BetLimits::default()
+        };
+        assert_eq!(round(true, &[10], limits).round_cap(), Multiplier(50_000));
+    }
+
+    #[test]
+    fn round_cap_keeps_exposure_under_the_limit() {
+        let state = round(true, &[1_000], exposure_limits(1_000));
+        let cap = state.round_cap();
+        // Winnings are rounded down, so 2.0009x still pays 2000
+        assert_eq!(cap, Multiplier(20_009));
+        assert!(state.exposure_at(cap) <= 1_000);
+        assert!(state.exposure_at(Multiplier(cap.0 + 1)) > 1_000);
+    }
+
+    #[test]
+    fn round_cap_rises_as_players_cash_out() {
+        let mut state = round(true, &[1_000, 1_000], exposure_limits(1_000));
+        assert_eq!(state.round_cap(), Multiplier(15_009));
+
+        let player = Identity::new("player0");
+        state
+            .minigame_verifiable
+            .players
+            .get_mut(&player)
+            .unwrap()
+            .cashed_out_at = Some(Multiplier::ONE);
+        assert_eq!(state.round_cap(), Multiplier(20_009));
+    }
+
+    #[test]
+    fn round_cap_counts_auto_cash_outs_at_their_target() {
+        let mut state = round(true, &[1_000, 1_000], exposure_limits(1_000));
+        let player = Identity::new("player0");
+        state
+            .minigame_verifiable
+            .players
+            .get_mut(&player)
+            .unwrap()
+            .auto_cash_out = Some(Multiplier::ONE);
+        assert_eq!(state.round_cap(), Multiplier(20_009));
+    }
+
+    #[test]
+    fn round_cap_without_exposure() {
+        // Only multipliers that round down to paying the bet back are left
+        let state = round(true, &[1_000], exposure_limits(0));
+        assert_eq!(state.round_cap(), Multiplier(10_009));
     }
 }
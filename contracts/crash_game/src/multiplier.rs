@@ -0,0 +1,114 @@
+use anyhow::{anyhow, Result};
+use borsh::{BorshDeserialize, BorshSerialize};
+use core::fmt;
+use serde::{Deserialize, Serialize};
+
+/// Fixed-point multiplier in basis points: `Multiplier(10_000)` is 1x.
+/// Only integer math is used, so the zkVM and the server always agree on the results.
+#[derive(
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+)]
+pub struct Multiplier(pub u64);
+
+/// `ln(2)` and `1` with 36 bits of fractional precision.
+const FRAC_BITS: u32 = 36;
+const FIXED_ONE: u128 = 1 << FRAC_BITS;
+const FIXED_LN_2: u128 = 47_632_711_549; // round(ln(2) * 2^36)
+
+impl Multiplier {
+    pub const BPS: u64 = 10_000;
+    pub const ONE: Multiplier = Multiplier(Self::BPS);
+    pub const MAX: Multiplier = Multiplier(u64::MAX);
+
+    /// `e^(numerator / denominator)`, rounded down to a basis point and saturating at `MAX`.
+    pub fn exp(numerator: u64, denominator: u64) -> Multiplier {
+        assert!(denominator > 0, "Denominator must be positive");
+        let x = ((numerator as u128) << FRAC_BITS) / denominator as u128;
+        // e^x = 2^n * e^r, with 0 <= r < ln(2) so the series converges quickly
+        let n = x / FIXED_LN_2;
+        let r = x % FIXED_LN_2;
+        if n >= 48 {
+            return Self::MAX;
+        }
+        let mut term = FIXED_ONE;
+        let mut sum = FIXED_ONE;
+        let mut k = 1;
+        while term > 0 {
+            term = term * r / FIXED_ONE / k;
+            sum += term;
+            k += 1;
+        }
+        let bps = ((sum << n) * Self::BPS as u128) >> FRAC_BITS;
+        u64::try_from(bps).map_or(Self::MAX, Multiplier)
+    }
+
+    /// `amount * self`, rounded down.
+    pub fn apply(self, amount: u64) -> Result<u64> {
+        u64::try_from(amount as u128 * self.0 as u128 / Self::BPS as u128)
+            .map_err(|_| anyhow!("{} times {} overflows", amount, self))
+    }
+}
+
+impl fmt::Display for Multiplier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:04}x", self.0 / Self::BPS, self.0 % Self::BPS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exp_known_values() {
+        assert_eq!(Multiplier::exp(0, 1), Multiplier::ONE);
+        assert_eq!(Multiplier::exp(1, 1), Multiplier(27_182));
+        assert_eq!(Multiplier::exp(1, 2), Multiplier(16_487));
+        assert_eq!(Multiplier::exp(2, 1), Multiplier(73_890));
+        // The crash game multiplier, e^(t / 5s)
+        assert_eq!(Multiplier::exp(5_000, 5_000), Multiplier(27_182));
+        assert_eq!(Multiplier::exp(23_025, 5_000), Multiplier(999_829));
+    }
+
+    #[test]
+    fn exp_is_monotonic() {
+        let mut previous = Multiplier::ONE;
+        for t in (0..200_000).step_by(7) {
+            let multiplier = Multiplier::exp(t, 5_000);
+            assert!(multiplier >= previous, "e^({} / 5000) went down", t);
+            previous = multiplier;
+        }
+    }
+
+    #[test]
+    fn exp_saturates_instead_of_overflowing() {
+        assert_eq!(Multiplier::exp(1_000_000, 5_000), Multiplier::MAX);
+        assert_eq!(Multiplier::exp(u64::MAX, 5_000), Multiplier::MAX);
+        assert_eq!(Multiplier::exp(u64::MAX, 1), Multiplier::MAX);
+    }
+
+    #[test]
+    fn apply_rounds_down() {
+        assert_eq!(Multiplier(15_000).apply(3).unwrap(), 4);
+        assert_eq!(Multiplier::ONE.apply(u64::MAX).unwrap(), u64::MAX);
+        assert!(Multiplier(20_000).apply(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Multiplier(12_345).to_string(), "1.2345x");
+        assert_eq!(Multiplier(1_000_000).to_string(), "100.0000x");
+    }
+}
@@ -8,6 +8,16 @@ impl fmt::Display for ChainEvent {
             ChainEvent::MinigameInitialized { player_count } => {
                 write!(f, "Minigame initialized with {} players", player_count)
             }
+            ChainEvent::RoundOpened => {
+                write!(f, "Standalone round opened")
+            }
+            ChainEvent::PlayerJoined { player_id, bet } => {
+                write!(f, "Player {} joined with a bet of {}", player_id, bet)
+            }
+            ChainEvent::AutoCashOutSet { player_id, target } => match target {
+                Some(target) => write!(f, "Player {} will cash out at {}", player_id, target),
+                None => write!(f, "Player {} cleared their auto cash-out", player_id),
+            },
             ChainEvent::GameStarted => {
                 write!(f, "Game started")
             }
@@ -18,12 +28,15 @@ impl fmt::Display for ChainEvent {
             } => {
                 write!(
                     f,
-                    "Player {} cashed out at {}x and won {}",
+                    "Player {} cashed out at {} and won {}",
                     player_id, multiplier, winnings
                 )
             }
             ChainEvent::GameCrashed { final_multiplier } => {
-                write!(f, "Game crashed at {}x", final_multiplier)
+                write!(f, "Game crashed at {}", final_multiplier)
+            }
+            ChainEvent::PayoutCapReached { multiplier } => {
+                write!(f, "Payout cap reached at {}", multiplier)
             }
             ChainEvent::MinigameEnded { final_results } => {
                 write!(
@@ -32,6 +45,30 @@ impl fmt::Display for ChainEvent {
                     final_results.len()
                 )
             }
+            ChainEvent::RoundSettled { payouts } => {
+                write!(f, "Round settled with {} payouts", payouts.len())
+            }
+            ChainEvent::MinigameAborted { final_results } => {
+                write!(
+                    f,
+                    "Minigame aborted, {} players keep their bets",
+                    final_results.len()
+                )
+            }
+            ChainEvent::LimitsUpdated { limits } => {
+                write!(
+                    f,
+                    "Bets limited to {}-{}, multipliers to {}, bankroll losses to {} per round",
+                    limits.min_bet, limits.max_bet, limits.max_multiplier, limits.max_exposure
+                )
+            }
+            ChainEvent::SeedChainCommitted { generation, length } => {
+                write!(
+                    f,
+                    "Seed chain {} committed for the next {} rounds",
+                    generation, length
+                )
+            }
         }
     }
 }
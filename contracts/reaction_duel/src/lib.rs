@@ -0,0 +1,627 @@
+use anyhow::{anyhow, Result};
+use board_game::game::{MinigameResult, PlayerMinigameResult};
+use board_game::GameActionBlob;
+use borsh::{BorshDeserialize, BorshSerialize};
+use sdk::caller::ExecutionContext;
+use sdk::utils::parse_calldata;
+use sdk::{
+    Blob, BlobData, BlobIndex, Calldata, ContractAction, ContractName, Identity, LaneId, RunResult,
+    StateCommitment, StructuredBlobData, ZkContract,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+pub mod utils;
+
+/// Longest the backend can wait before "go", counted from the start of the duel.
+pub const MAX_GO_DELAY_MS: u64 = 10_000;
+/// How long players have to tap after "go" before the backend can reveal it.
+pub const REACTION_WINDOW_MS: u128 = 3_000;
+/// How long a duel must sit without any transaction before anyone can abort it.
+pub const ABORT_TIMEOUT_MS: u128 = 5 * 60 * 1000;
+
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct Duelist {
+    pub id: Identity,
+    pub name: String,
+    pub bet: u64,
+    /// Timestamp of the transaction carrying the player's tap. Only the first tap counts.
+    pub tapped_at: Option<u128>,
+}
+
+#[derive(
+    Default, Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq,
+)]
+pub enum DuelPhase {
+    #[default]
+    Uninitialized,
+    WaitingForGo,
+    Revealed,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct DuelInstance {
+    pub phase: DuelPhase,
+    pub players: BTreeMap<Identity, Duelist>,
+    /// Hash of the go delay, see `go_commitment`.
+    pub go_commitment: [u8; 32],
+    pub started_at: u128,
+    /// Revealed go time: taps before it are false starts.
+    pub go_at: Option<u128>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct GameState {
+    pub duel: DuelInstance,
+    pub board_contract: ContractName,
+    pub backend_identity: Identity,
+    pub last_interaction_time: u128,
+    pub lane_id: LaneId,
+}
+
+// Actions that can be performed on-chain
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub enum ChainAction {
+    InitMinigame {
+        players: Vec<(Identity, String, u64)>,
+        go_commitment: [u8; 32],
+    },
+    Tap,
+    /// Backend only: reveals when "go" was, which settles the duel.
+    RevealGo {
+        delay_ms: u64,
+        salt: [u8; 32],
+    },
+    Done,
+    /// Anyone can end a duel the backend never revealed, see `ABORT_TIMEOUT_MS`.
+    Abort,
+}
+
+// Events that are recorded on-chain
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub enum ChainEvent {
+    MinigameInitialized {
+        players: Vec<Identity>,
+    },
+    PlayerTapped {
+        player_id: Identity,
+    },
+    GoRevealed {
+        go_at: u128,
+        winner: Option<Identity>,
+    },
+    MinigameEnded {
+        final_results: Vec<(Identity, i32)>,
+    },
+    MinigameAborted {
+        final_results: Vec<(Identity, i32)>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct ChainActionBlob(pub u128, pub ChainAction);
+
+impl ContractAction for ChainActionBlob {
+    fn as_blob(
+        &self,
+        contract_name: ContractName,
+        caller: Option<BlobIndex>,
+        callees: Option<Vec<BlobIndex>>,
+    ) -> Blob {
+        Blob {
+            contract_name,
+            data: BlobData::from(StructuredBlobData {
+                caller,
+                callees,
+                parameters: self.clone(),
+            }),
+        }
+    }
+}
+
+/// `sha256(delay_ms || salt)`, with the delay in little endian.
+pub fn go_commitment(delay_ms: u64, salt: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(delay_ms.to_le_bytes());
+    hasher.update(salt);
+    hasher.finalize().into()
+}
+
+impl sdk::FullStateRevert for GameState {}
+
+impl ZkContract for GameState {
+    fn execute(&mut self, contract_input: &Calldata) -> RunResult {
+        let (action, mut exec_ctx) =
+            parse_calldata::<ChainActionBlob>(contract_input).map_err(|e| e.to_string())?;
+
+        // Not an identity provider
+        if contract_input
+            .identity
+            .0
+            .ends_with(&exec_ctx.contract_name.0)
+        {
+            return Err("Invalid identity provider".to_string());
+        }
+
+        let Some(ref ctx) = contract_input.tx_ctx else {
+            return Err("Missing transaction context".into());
+        };
+
+        // Rollup mode, ensure everything is sent to the same lane ID or we are well past interaction timeout
+        let interaction_timeout = ctx.timestamp.0.saturating_add(60 * 60 * 24 * 1000); // 24 hours
+        if self.lane_id == LaneId::default() || ctx.timestamp.0 > interaction_timeout {
+            self.lane_id = ctx.lane_id.clone();
+        } else if self.lane_id != ctx.lane_id {
+            return Err("Invalid lane ID".into());
+        }
+
+        let events = self
+            .process_chain_action(
+                &contract_input.identity,
+                &action.1,
+                ctx.timestamp.0,
+                Some((&action, &mut exec_ctx)),
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.last_interaction_time = ctx.timestamp.0;
+
+        Ok((borsh::to_vec(&events).unwrap(), exec_ctx, vec![]))
+    }
+
+    fn commit(&self) -> StateCommitment {
+        StateCommitment(borsh::to_vec(self).unwrap())
+    }
+}
+
+impl GameState {
+    pub fn new(board_contract: ContractName, backend_identity: Identity) -> Self {
+        Self {
+            duel: DuelInstance::default(),
+            board_contract,
+            backend_identity,
+            last_interaction_time: 0,
+            lane_id: LaneId::default(),
+        }
+    }
+
+    /// `timestamp` is the one of the transaction, it decides who tapped first.
+    pub fn process_chain_action(
+        &mut self,
+        identity: &Identity,
+        action: &ChainAction,
+        timestamp: u128,
+        ctx: Option<(&ChainActionBlob, &mut ExecutionContext)>,
+    ) -> Result<Vec<ChainEvent>> {
+        let mut events = Vec::new();
+
+        match action {
+            ChainAction::InitMinigame {
+                players,
+                go_commitment,
+            } => {
+                if self.duel.phase != DuelPhase::Uninitialized {
+                    return Err(anyhow!("Duel is already in progress"));
+                }
+
+                if players.len() != 2 {
+                    return Err(anyhow!("A duel needs exactly two players"));
+                }
+
+                if let Some((blob, exec_ctx)) = ctx {
+                    let expected_board_blob = GameActionBlob(
+                        blob.0,
+                        board_game::game::GameAction::StartMinigame {
+                            minigame: exec_ctx.contract_name.clone(),
+                            players: players.clone(),
+                        },
+                    );
+                    // Check our data matches the board contract
+                    exec_ctx
+                        .is_in_callee_blobs(&self.board_contract, expected_board_blob)
+                        .map_err(|_| {
+                            anyhow!("Missing or incorrect board game StartMinigame action in transaction",)
+                        })?;
+                }
+
+                for (id, name, bet) in players {
+                    self.duel.players.insert(
+                        id.clone(),
+                        Duelist {
+                            id: id.clone(),
+                            name: name.clone(),
+                            bet: *bet,
+                            tapped_at: None,
+                        },
+                    );
+                }
+
+                self.duel.phase = DuelPhase::WaitingForGo;
+                self.duel.go_commitment = *go_commitment;
+                self.duel.started_at = timestamp;
+
+                events.push(ChainEvent::MinigameInitialized {
+                    players: self.duel.players.keys().cloned().collect(),
+                });
+            }
+
+            ChainAction::Tap => {
+                if self.duel.phase != DuelPhase::WaitingForGo {
+                    return Err(anyhow!("Duel is not running"));
+                }
+
+                let Some(player) = self.duel.players.get_mut(identity) else {
+                    return Err(anyhow!("Player not found"));
+                };
+
+                if player.tapped_at.is_some() {
+                    return Err(anyhow!("Player already tapped"));
+                }
+
+                player.tapped_at = Some(timestamp);
+                events.push(ChainEvent::PlayerTapped {
+                    player_id: identity.clone(),
+                });
+            }
+
+            ChainAction::RevealGo { delay_ms, salt } => {
+                if identity != &self.backend_identity {
+                    return Err(anyhow!(
+                        "Only the backend can reveal the go time: {} vs {}",
+                        identity,
+                        self.backend_identity
+                    ));
+                }
+
+                if self.duel.phase != DuelPhase::WaitingForGo {
+                    return Err(anyhow!("Duel is not running"));
+                }
+
+                if go_commitment(*delay_ms, salt) != self.duel.go_commitment {
+                    return Err(anyhow!("Go time does not match the commitment"));
+                }
+
+                if *delay_ms > MAX_GO_DELAY_MS {
+                    return Err(anyhow!("Go delay is too long"));
+                }
+
+                let go_at = self.duel.started_at + *delay_ms as u128;
+                // Don't cut players off before they had a chance to react
+                let everyone_tapped = self.duel.players.values().all(|p| p.tapped_at.is_some());
+                if !everyone_tapped && timestamp < go_at + REACTION_WINDOW_MS {
+                    return Err(anyhow!("Players can still tap"));
+                }
+
+                self.duel.go_at = Some(go_at);
+                self.duel.phase = DuelPhase::Revealed;
+
+                events.push(ChainEvent::GoRevealed {
+                    go_at,
+                    winner: self.winner().cloned(),
+                });
+            }
+
+            ChainAction::Done => {
+                if self.duel.phase != DuelPhase::Revealed {
+                    return Err(anyhow!(
+                        "Cannot end minigame before the go time is revealed"
+                    ));
+                }
+                let expected_final_results = self.final_results()?;
+                if let Some((blob, exec_ctx)) = ctx {
+                    self.check_board_end(blob, exec_ctx, &expected_final_results)?;
+                }
+
+                self.duel = DuelInstance::default();
+                events.push(ChainEvent::MinigameEnded {
+                    final_results: expected_final_results,
+                });
+            }
+
+            ChainAction::Abort => {
+                if self.duel.phase != DuelPhase::WaitingForGo {
+                    return Err(anyhow!("No duel in progress to abort"));
+                }
+
+                if timestamp.saturating_sub(self.last_interaction_time) < ABORT_TIMEOUT_MS {
+                    return Err(anyhow!("The duel is not stalled yet"));
+                }
+
+                // Nobody wins or loses anything, as if the duel never happened.
+                let final_results = self.aborted_results();
+                if let Some((blob, exec_ctx)) = ctx {
+                    self.check_board_end(blob, exec_ctx, &final_results)?;
+                }
+
+                self.duel = DuelInstance::default();
+                events.push(ChainEvent::MinigameAborted { final_results });
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Earliest tap at or after the go time. Taps before it are false starts, and a tie is a draw.
+    pub fn winner(&self) -> Option<&Identity> {
+        let go_at = self.duel.go_at?;
+        let mut valid_taps = self
+            .duel
+            .players
+            .values()
+            .filter_map(|p| p.tapped_at.filter(|&t| t >= go_at).map(|t| (t, &p.id)))
+            .collect::<Vec<_>>();
+        valid_taps.sort();
+        match valid_taps.as_slice() {
+            [(first, _), (second, _), ..] if first == second => None,
+            [(_, winner), ..] => Some(*winner),
+            [] => None,
+        }
+    }
+
+    /// Results of an aborted duel: both players keep their coins.
+    pub fn aborted_results(&self) -> Vec<(Identity, i32)> {
+        self.duel.players.keys().map(|id| (id.clone(), 0)).collect()
+    }
+
+    /// When ending the minigame, verify that the board game is being updated with the correct data
+    fn check_board_end(
+        &self,
+        blob: &ChainActionBlob,
+        exec_ctx: &mut ExecutionContext,
+        final_results: &[(Identity, i32)],
+    ) -> Result<()> {
+        let expected_board_blob = GameActionBlob(
+            blob.0,
+            board_game::game::GameAction::EndMinigame {
+                result: MinigameResult {
+                    contract_name: exec_ctx.contract_name.clone(),
+                    player_results: final_results
+                        .iter()
+                        .map(|r| PlayerMinigameResult {
+                            player_id: r.0.clone(),
+                            coins_delta: r.1,
+                        })
+                        .collect(),
+                },
+            },
+        );
+
+        exec_ctx
+            .is_in_callee_blobs(&self.board_contract, expected_board_blob.clone())
+            .map_err(|_| {
+                anyhow!(
+                    "Missing board game EndMinigame action in transaction, expected: {:?}",
+                    expected_board_blob
+                )
+            })
+    }
+
+    pub fn get_end_results(&self) -> Result<Vec<(Identity, i32)>> {
+        if self.duel.phase != DuelPhase::Revealed {
+            return Err(anyhow!("Duel is still running"));
+        }
+        self.final_results()
+    }
+
+    /// The winner takes the opponent's bet. Nobody wins or loses anything on a draw.
+    pub fn final_results(&self) -> Result<Vec<(Identity, i32)>> {
+        let winner = self.winner();
+        self.duel
+            .players
+            .values()
+            .map(|player| {
+                let delta = match winner {
+                    None => 0,
+                    Some(winner) if winner == &player.id => self
+                        .duel
+                        .players
+                        .values()
+                        .filter(|p| &p.id != winner)
+                        .map(|p| p.bet as i128)
+                        .sum(),
+                    Some(_) => -(player.bet as i128),
+                };
+                let delta = i32::try_from(delta).map_err(|_| {
+                    anyhow!("Result for {} does not fit in a coin delta", player.id)
+                })?;
+                Ok((player.id.clone(), delta))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STARTED_AT: u128 = 1_000;
+    const DELAY_MS: u64 = 2_000;
+    const GO_AT: u128 = STARTED_AT + DELAY_MS as u128;
+    const SALT: [u8; 32] = [7; 32];
+
+    fn player(i: usize) -> Identity {
+        Identity::new(format!("player{}", i))
+    }
+
+    fn backend() -> Identity {
+        Identity::new("backend")
+    }
+
+    /// Duel waiting for its go time, started at `STARTED_AT`.
+    fn duel(bets: [u64; 2], delay_ms: u64) -> GameState {
+        let mut state = GameState::new(ContractName::new("board_game"), backend());
+        let players = bets
+            .iter()
+            .enumerate()
+            .map(|(i, &bet)| (player(i), format!("Player {}", i), bet))
+            .collect();
+        state
+            .process_chain_action(
+                &backend(),
+                &ChainAction::InitMinigame {
+                    players,
+                    go_commitment: go_commitment(delay_ms, &SALT),
+                },
+                STARTED_AT,
+                None,
+            )
+            .unwrap();
+        state
+    }
+
+    fn tap(state: &mut GameState, i: usize, timestamp: u128) -> Result<Vec<ChainEvent>> {
+        state.process_chain_action(&player(i), &ChainAction::Tap, timestamp, None)
+    }
+
+    fn reveal(state: &mut GameState, delay_ms: u64, timestamp: u128) -> Result<Vec<ChainEvent>> {
+        state.process_chain_action(
+            &backend(),
+            &ChainAction::RevealGo {
+                delay_ms,
+                salt: SALT,
+            },
+            timestamp,
+            None,
+        )
+    }
+
+    #[test]
+    fn duel_needs_two_players() {
+        let mut state = GameState::new(ContractName::new("board_game"), backend());
+        let init = ChainAction::InitMinigame {
+            players: vec![(player(0), "Player 0".to_string(), 10)],
+            go_commitment: go_commitment(DELAY_MS, &SALT),
+        };
+        assert!(state
+            .process_chain_action(&backend(), &init, STARTED_AT, None)
+            .is_err());
+    }
+
+    #[test]
+    fn reveal_must_match_the_commitment() {
+        let mut state = duel([10, 10], DELAY_MS);
+        tap(&mut state, 0, GO_AT + 100).unwrap();
+        tap(&mut state, 1, GO_AT + 200).unwrap();
+
+        assert!(reveal(&mut state, DELAY_MS + 1, GO_AT + 300).is_err());
+        let wrong_salt = ChainAction::RevealGo {
+            delay_ms: DELAY_MS,
+            salt: [8; 32],
+        };
+        assert!(state
+            .process_chain_action(&backend(), &wrong_salt, GO_AT + 300, None)
+            .is_err());
+        let from_player = ChainAction::RevealGo {
+            delay_ms: DELAY_MS,
+            salt: SALT,
+        };
+        assert!(state
+            .process_chain_action(&player(0), &from_player, GO_AT + 300, None)
+            .is_err());
+
+        reveal(&mut state, DELAY_MS, GO_AT + 300).unwrap();
+        assert_eq!(state.duel.go_at, Some(GO_AT));
+    }
+
+    #[test]
+    fn go_delay_is_bounded() {
+        let delay_ms = MAX_GO_DELAY_MS + 1;
+        let mut state = duel([10, 10], delay_ms);
+        let late = STARTED_AT + delay_ms as u128 + REACTION_WINDOW_MS;
+        assert!(reveal(&mut state, delay_ms, late).is_err());
+
+        let mut state = duel([10, 10], MAX_GO_DELAY_MS);
+        let late = STARTED_AT + MAX_GO_DELAY_MS as u128 + REACTION_WINDOW_MS;
+        assert!(reveal(&mut state, MAX_GO_DELAY_MS, late).is_ok());
+    }
+
+    #[test]
+    fn reveal_waits_for_the_reaction_window() {
+        let mut state = duel([10, 10], DELAY_MS);
+        tap(&mut state, 0, GO_AT + 100).unwrap();
+        assert!(reveal(&mut state, DELAY_MS, GO_AT + REACTION_WINDOW_MS - 1).is_err());
+        reveal(&mut state, DELAY_MS, GO_AT + REACTION_WINDOW_MS).unwrap();
+        assert_eq!(state.winner(), Some(&player(0)));
+
+        // No need to wait once both players tapped
+        let mut state = duel([10, 10], DELAY_MS);
+        tap(&mut state, 0, GO_AT + 100).unwrap();
+        tap(&mut state, 1, GO_AT + 200).unwrap();
+        assert!(reveal(&mut state, DELAY_MS, GO_AT + 300).is_ok());
+    }
+
+    #[test]
+    fn taps_before_go_are_false_starts() {
+        let mut state = duel([10, 30], DELAY_MS);
+        tap(&mut state, 0, GO_AT - 1).unwrap();
+        tap(&mut state, 1, GO_AT + 500).unwrap();
+        // Only the first tap counts
+        assert!(tap(&mut state, 0, GO_AT + 100).is_err());
+        reveal(&mut state, DELAY_MS, GO_AT + 600).unwrap();
+
+        assert_eq!(state.winner(), Some(&player(1)));
+        assert_eq!(
+            state.get_end_results().unwrap(),
+            vec![(player(0), -10), (player(1), 10)]
+        );
+    }
+
+    #[test]
+    fn winner_takes_the_opponent_bet() {
+        let mut state = duel([10, 30], DELAY_MS);
+        tap(&mut state, 0, GO_AT).unwrap();
+        tap(&mut state, 1, GO_AT + 1).unwrap();
+        reveal(&mut state, DELAY_MS, GO_AT + 1).unwrap();
+
+        assert_eq!(state.winner(), Some(&player(0)));
+        assert_eq!(
+            state.get_end_results().unwrap(),
+            vec![(player(0), 30), (player(1), -30)]
+        );
+    }
+
+    #[test]
+    fn equal_taps_are_a_draw() {
+        let mut state = duel([10, 30], DELAY_MS);
+        tap(&mut state, 0, GO_AT + 100).unwrap();
+        tap(&mut state, 1, GO_AT + 100).unwrap();
+        reveal(&mut state, DELAY_MS, GO_AT + 100).unwrap();
+
+        assert_eq!(state.winner(), None);
+        assert_eq!(
+            state.get_end_results().unwrap(),
+            vec![(player(0), 0), (player(1), 0)]
+        );
+    }
+
+    #[test]
+    fn abort_only_after_timeout() {
+        let mut state = duel([10, 10], DELAY_MS);
+        state.last_interaction_time = STARTED_AT;
+
+        let anyone = Identity::new("anyone");
+        let stalled = STARTED_AT + ABORT_TIMEOUT_MS;
+        assert!(state
+            .process_chain_action(&anyone, &ChainAction::Abort, stalled - 1, None)
+            .is_err());
+        let events = state
+            .process_chain_action(&anyone, &ChainAction::Abort, stalled, None)
+            .unwrap();
+        assert!(matches!(
+            &events[..],
+            [ChainEvent::MinigameAborted { final_results }]
+                if final_results == &vec![(player(0), 0), (player(1), 0)]
+        ));
+        assert_eq!(state.duel.phase, DuelPhase::Uninitialized);
+
+        // A revealed duel settles through Done instead
+        let mut state = duel([10, 10], DELAY_MS);
+        tap(&mut state, 0, GO_AT + 100).unwrap();
+        tap(&mut state, 1, GO_AT + 200).unwrap();
+        reveal(&mut state, DELAY_MS, GO_AT + 300).unwrap();
+        assert!(state
+            .process_chain_action(&anyone, &ChainAction::Abort, stalled, None)
+            .is_err());
+    }
+}
@@ -0,0 +1,34 @@
+use core::fmt;
+
+use crate::ChainEvent;
+
+impl fmt::Display for ChainEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainEvent::MinigameInitialized { players } => {
+                write!(f, "Duel initialized between {:?}", players)
+            }
+            ChainEvent::PlayerTapped { player_id } => {
+                write!(f, "Player {} tapped", player_id)
+            }
+            ChainEvent::GoRevealed { go_at, winner } => match winner {
+                Some(winner) => write!(f, "Go was at {}, player {} won", go_at, winner),
+                None => write!(f, "Go was at {}, the duel is a draw", go_at),
+            },
+            ChainEvent::MinigameEnded { final_results } => {
+                write!(
+                    f,
+                    "Minigame ended with {} player results",
+                    final_results.len()
+                )
+            }
+            ChainEvent::MinigameAborted { final_results } => {
+                write!(
+                    f,
+                    "Minigame aborted, {} players keep their bets",
+                    final_results.len()
+                )
+            }
+        }
+    }
+}
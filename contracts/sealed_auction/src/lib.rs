@@ -0,0 +1,705 @@
+use anyhow::{anyhow, Result};
+use board_game::game::{MinigameResult, PlayerMinigameResult};
+use board_game::GameActionBlob;
+use borsh::{BorshDeserialize, BorshSerialize};
+use sdk::caller::ExecutionContext;
+use sdk::utils::parse_calldata;
+use sdk::{
+    Blob, BlobData, BlobIndex, Calldata, ContractAction, ContractName, Identity, LaneId, RunResult,
+    StateCommitment, StructuredBlobData, ZkContract,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+pub mod utils;
+
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct Bidder {
+    pub id: Identity,
+    pub name: String,
+    /// Coins the player bet on the board, lost unless they win the auction.
+    pub bet: u64,
+    pub commitment: Option<[u8; 32]>,
+    pub bid: Option<u64>,
+}
+
+#[derive(
+    Default, Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize, PartialEq, Eq,
+)]
+pub enum AuctionPhase {
+    #[default]
+    Uninitialized,
+    Bidding,
+    Revealing,
+    Closed,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct AuctionInstance {
+    pub phase: AuctionPhase,
+    pub bidders: BTreeMap<Identity, Bidder>,
+    /// Bidders in the order they revealed, the earliest reveal wins ties.
+    pub reveal_order: Vec<Identity>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct GameState {
+    pub auction: AuctionInstance,
+    pub board_contract: ContractName,
+    pub backend_identity: Identity,
+    pub last_interaction_time: u128,
+    pub lane_id: LaneId,
+}
+
+// Actions that can be performed on-chain
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub enum ChainAction {
+    InitMinigame {
+        players: Vec<(Identity, String, u64)>,
+    },
+    /// Hidden bid, see `bid_commitment`.
+    CommitBid {
+        commitment: [u8; 32],
+    },
+    /// Backend only: ends bidding for players who did not commit in time.
+    StartReveal,
+    RevealBid {
+        amount: u64,
+        salt: [u8; 32],
+    },
+    /// Backend only: ends the reveal phase for players who did not reveal in time.
+    Close,
+    Done,
+    /// Anyone can end an auction the backend stopped moving, see `ABORT_TIMEOUT_MS`.
+    Abort,
+}
+
+// Events that are recorded on-chain
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub enum ChainEvent {
+    MinigameInitialized {
+        player_count: usize,
+    },
+    BidCommitted {
+        player_id: Identity,
+    },
+    RevealStarted,
+    BidRevealed {
+        player_id: Identity,
+        amount: u64,
+    },
+    AuctionClosed {
+        winner: Option<Identity>,
+        winning_bid: u64,
+        prize: u64,
+    },
+    MinigameEnded {
+        final_results: Vec<(Identity, i32)>,
+    },
+    MinigameAborted {
+        final_results: Vec<(Identity, i32)>,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, BorshSerialize, BorshDeserialize)]
+pub struct ChainActionBlob(pub u128, pub ChainAction);
+
+impl ContractAction for ChainActionBlob {
+    fn as_blob(
+        &self,
+        contract_name: ContractName,
+        caller: Option<BlobIndex>,
+        callees: Option<Vec<BlobIndex>>,
+    ) -> Blob {
+        Blob {
+            contract_name,
+            data: BlobData::from(StructuredBlobData {
+                caller,
+                callees,
+                parameters: self.clone(),
+            }),
+        }
+    }
+}
+
+/// How long an auction must sit without any transaction before anyone can abort it.
+pub const ABORT_TIMEOUT_MS: u128 = 5 * 60 * 1000;
+
+/// `sha256(amount || salt || identity)`, with the amount in little endian.
+/// Binding the identity stops players from copying someone else's commitment and reveal.
+pub fn bid_commitment(amount: u64, salt: &[u8; 32], identity: &Identity) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(amount.to_le_bytes());
+    hasher.update(salt);
+    hasher.update(identity.0.as_bytes());
+    hasher.finalize().into()
+}
+
+impl sdk::FullStateRevert for GameState {}
+
+impl ZkContract for GameState {
+    fn execute(&mut self, contract_input: &Calldata) -> RunResult {
+        let (action, mut exec_ctx) =
+            parse_calldata::<ChainActionBlob>(contract_input).map_err(|e| e.to_string())?;
+
+        // Not an identity provider
+        if contract_input
+            .identity
+            .0
+            .ends_with(&exec_ctx.contract_name.0)
+        {
+            return Err("Invalid identity provider".to_string());
+        }
+
+        let Some(ref ctx) = contract_input.tx_ctx else {
+            return Err("Missing transaction context".into());
+        };
+
+        // Rollup mode, ensure everything is sent to the same lane ID or we are well past interaction timeout
+        let interaction_timeout = ctx.timestamp.0.saturating_add(60 * 60 * 24 * 1000); // 24 hours
+        if self.lane_id == LaneId::default() || ctx.timestamp.0 > interaction_timeout {
+            self.lane_id = ctx.lane_id.clone();
+        } else if self.lane_id != ctx.lane_id {
+            return Err("Invalid lane ID".into());
+        }
+
+        let events = self
+            .process_chain_action(
+                &contract_input.identity,
+                &action.1,
+                ctx.timestamp.0,
+                Some((&action, &mut exec_ctx)),
+            )
+            .map_err(|e| e.to_string())?;
+
+        self.last_interaction_time = ctx.timestamp.0;
+
+        Ok((borsh::to_vec(&events).unwrap(), exec_ctx, vec![]))
+    }
+
+    fn commit(&self) -> StateCommitment {
+        StateCommitment(borsh::to_vec(self).unwrap())
+    }
+}
+
+impl GameState {
+    pub fn new(board_contract: ContractName, backend_identity: Identity) -> Self {
+        Self {
+            auction: AuctionInstance::default(),
+            board_contract,
+            backend_identity,
+            last_interaction_time: 0,
+            lane_id: LaneId::default(),
+        }
+    }
+
+    /// `timestamp` is the one of the transaction, it decides when a stalled auction can be aborted.
+    pub fn process_chain_action(
+        &mut self,
+        identity: &Identity,
+        action: &ChainAction,
+        timestamp: u128,
+        ctx: Option<(&ChainActionBlob, &mut ExecutionContext)>,
+    ) -> Result<Vec<ChainEvent>> {
+        let mut events = Vec::new();
+
+        match action {
+            ChainAction::InitMinigame { players } => {
+                if self.auction.phase != AuctionPhase::Uninitialized {
+                    return Err(anyhow!("Auction is already in progress"));
+                }
+
+                if let Some((blob, exec_ctx)) = ctx {
+                    let expected_board_blob = GameActionBlob(
+                        blob.0,
+                        board_game::game::GameAction::StartMinigame {
+                            minigame: exec_ctx.contract_name.clone(),
+                            players: players.clone(),
+                        },
+                    );
+                    // Check our data matches the board contract
+                    exec_ctx
+                        .is_in_callee_blobs(&self.board_contract, expected_board_blob)
+                        .map_err(|_| {
+                            anyhow!("Missing or incorrect board game StartMinigame action in transaction",)
+                        })?;
+                }
+
+                for (id, name, bet) in players {
+                    self.auction.bidders.insert(
+                        id.clone(),
+                        Bidder {
+                            id: id.clone(),
+                            name: name.clone(),
+                            bet: *bet,
+                            commitment: None,
+                            bid: None,
+                        },
+                    );
+                }
+
+                self.auction.phase = AuctionPhase::Bidding;
+
+                events.push(ChainEvent::MinigameInitialized {
+                    player_count: players.len(),
+                });
+            }
+
+            ChainAction::CommitBid { commitment } => {
+                if self.auction.phase != AuctionPhase::Bidding {
+                    return Err(anyhow!("Bidding is closed"));
+                }
+
+                let Some(bidder) = self.auction.bidders.get_mut(identity) else {
+                    return Err(anyhow!("Player not found"));
+                };
+
+                if bidder.commitment.is_some() {
+                    return Err(anyhow!("Bid already committed"));
+                }
+
+                bidder.commitment = Some(*commitment);
+                events.push(ChainEvent::BidCommitted {
+                    player_id: identity.clone(),
+                });
+
+                // No need to wait for the backend once everyone has bid
+                if self
+                    .auction
+                    .bidders
+                    .values()
+                    .all(|b| b.commitment.is_some())
+                {
+                    self.auction.phase = AuctionPhase::Revealing;
+                    events.push(ChainEvent::RevealStarted);
+                }
+            }
+
+            ChainAction::StartReveal => {
+                if identity != &self.backend_identity {
+                    return Err(anyhow!(
+                        "Only the backend can start the reveal: {} vs {}",
+                        identity,
+                        self.backend_identity
+                    ));
+                }
+
+                if self.auction.phase != AuctionPhase::Bidding {
+                    return Err(anyhow!("Bidding is closed"));
+                }
+
+                self.auction.phase = AuctionPhase::Revealing;
+                events.push(ChainEvent::RevealStarted);
+            }
+
+            ChainAction::RevealBid { amount, salt } => {
+                if self.auction.phase != AuctionPhase::Revealing {
+                    return Err(anyhow!("Auction is not revealing bids"));
+                }
+
+                let Some(bidder) = self.auction.bidders.get_mut(identity) else {
+                    return Err(anyhow!("Player not found"));
+                };
+
+                let Some(commitment) = bidder.commitment else {
+                    return Err(anyhow!("No bid committed"));
+                };
+
+                if bidder.bid.is_some() {
+                    return Err(anyhow!("Bid already revealed"));
+                }
+
+                if bid_commitment(*amount, salt, identity) != commitment {
+                    return Err(anyhow!("Bid does not match the commitment"));
+                }
+
+                if *amount > bidder.bet {
+                    return Err(anyhow!("Bid cannot exceed the player's bet"));
+                }
+
+                bidder.bid = Some(*amount);
+                self.auction.reveal_order.push(identity.clone());
+                events.push(ChainEvent::BidRevealed {
+                    player_id: identity.clone(),
+                    amount: *amount,
+                });
+
+                // Close as soon as every committed bid is revealed
+                if self
+                    .auction
+                    .bidders
+                    .values()
+                    .all(|b| b.commitment.is_none() || b.bid.is_some())
+                {
+                    events.push(self.close());
+                }
+            }
+
+            ChainAction::Close => {
+                if identity != &self.backend_identity {
+                    return Err(anyhow!(
+                        "Only the backend can close the auction: {} vs {}",
+                        identity,
+                        self.backend_identity
+                    ));
+                }
+
+                if self.auction.phase != AuctionPhase::Revealing {
+                    return Err(anyhow!("Auction is not revealing bids"));
+                }
+
+                events.push(self.close());
+            }
+
+            ChainAction::Done => {
+                if self.auction.phase != AuctionPhase::Closed {
+                    return Err(anyhow!("Cannot end minigame before the auction is closed"));
+                }
+                let expected_final_results = self.final_results()?;
+                if let Some((blob, exec_ctx)) = ctx {
+                    self.check_board_end(blob, exec_ctx, &expected_final_results)?;
+                }
+
+                self.auction = AuctionInstance::default();
+                events.push(ChainEvent::MinigameEnded {
+                    final_results: expected_final_results,
+                });
+            }
+
+            ChainAction::Abort => {
+                if !matches!(
+                    self.auction.phase,
+                    AuctionPhase::Bidding | AuctionPhase::Revealing
+                ) {
+                    return Err(anyhow!("No auction in progress to abort"));
+                }
+
+                if timestamp.saturating_sub(self.last_interaction_time) < ABORT_TIMEOUT_MS {
+                    return Err(anyhow!("The auction is not stalled yet"));
+                }
+
+                // Nobody wins or loses anything, as if the auction never happened.
+                let final_results = self.aborted_results();
+                if let Some((blob, exec_ctx)) = ctx {
+                    self.check_board_end(blob, exec_ctx, &final_results)?;
+                }
+
+                self.auction = AuctionInstance::default();
+                events.push(ChainEvent::MinigameAborted { final_results });
+            }
+        }
+
+        Ok(events)
+    }
+
+    fn close(&mut self) -> ChainEvent {
+        self.auction.phase = AuctionPhase::Closed;
+        let winner = self.winner().cloned();
+        ChainEvent::AuctionClosed {
+            winning_bid: winner
+                .as_ref()
+                .and_then(|id| self.auction.bidders.get(id))
+                .and_then(|b| b.bid)
+                .unwrap_or(0),
+            prize: winner.as_ref().map(|id| self.prize(id)).unwrap_or(0),
+            winner,
+        }
+    }
+
+    /// Highest revealed bid, the earliest reveal wins ties.
+    pub fn winner(&self) -> Option<&Identity> {
+        self.auction
+            .reveal_order
+            .iter()
+            .rev()
+            .max_by_key(|id| self.auction.bidders.get(*id).and_then(|b| b.bid))
+    }
+
+    /// The bets of everyone but the winner.
+    fn prize(&self, winner: &Identity) -> u64 {
+        self.auction
+            .bidders
+            .values()
+            .filter(|b| &b.id != winner)
+            .map(|b| b.bet)
+            .sum()
+    }
+
+    /// Results of an aborted auction: every player keeps their coins.
+    pub fn aborted_results(&self) -> Vec<(Identity, i32)> {
+        self.auction
+            .bidders
+            .keys()
+            .map(|id| (id.clone(), 0))
+            .collect()
+    }
+
+    /// When ending the minigame, verify that the board game is being updated with the correct data
+    fn check_board_end(
+        &self,
+        blob: &ChainActionBlob,
+        exec_ctx: &mut ExecutionContext,
+        final_results: &[(Identity, i32)],
+    ) -> Result<()> {
+        let expected_board_blob = GameActionBlob(
+            blob.0,
+            board_game::game::GameAction::EndMinigame {
+                result: MinigameResult {
+                    contract_name: exec_ctx.contract_name.clone(),
+                    player_results: final_results
+                        .iter()
+                        .map(|r| PlayerMinigameResult {
+                            player_id: r.0.clone(),
+                            coins_delta: r.1,
+                        })
+                        .collect(),
+                },
+            },
+        );
+
+        exec_ctx
+            .is_in_callee_blobs(&self.board_contract, expected_board_blob.clone())
+            .map_err(|_| {
+                anyhow!(
+                    "Missing board game EndMinigame action in transaction, expected: {:?}",
+                    expected_board_blob
+                )
+            })
+    }
+
+    pub fn get_end_results(&self) -> Result<Vec<(Identity, i32)>> {
+        if self.auction.phase != AuctionPhase::Closed {
+            return Err(anyhow!("Auction is still running"));
+        }
+        self.final_results()
+    }
+
+    /// The winner pays their bid and wins the other players' bets, everyone else loses their bet.
+    /// Without a single revealed bid, nobody wins or loses anything.
+    pub fn final_results(&self) -> Result<Vec<(Identity, i32)>> {
+        let winner = self.winner();
+        self.auction
+            .bidders
+            .values()
+            .map(|bidder| {
+                let delta = match winner {
+                    None => 0,
+                    Some(winner) if winner == &bidder.id => {
+                        self.prize(winner) as i128 - bidder.bid.unwrap_or(0) as i128
+                    }
+                    Some(_) => -(bidder.bet as i128),
+                };
+                let delta = i32::try_from(delta).map_err(|_| {
+                    anyhow!("Result for {} does not fit in a coin delta", bidder.id)
+                })?;
+                Ok((bidder.id.clone(), delta))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(i: usize) -> Identity {
+        Identity::new(format!("player{}", i))
+    }
+
+    /// Auction taking bids, with one player per bet.
+    fn auction(bets: &[u64]) -> GameState {
+        let mut state = GameState::new(ContractName::new("board_game"), Identity::new("backend"));
+        let players = bets
+            .iter()
+            .enumerate()
+            .map(|(i, &bet)| (player(i), format!("Player {}", i), bet))
+            .collect();
+        state
+            .process_chain_action(
+                &Identity::new("backend"),
+                &ChainAction::InitMinigame { players },
+                0,
+                None,
+            )
+            .unwrap();
+        state
+    }
+
+    fn commit(state: &mut GameState, i: usize, amount: u64) -> Result<Vec<ChainEvent>> {
+        let commitment = bid_commitment(amount, &[i as u8; 32], &player(i));
+        state.process_chain_action(&player(i), &ChainAction::CommitBid { commitment }, 0, None)
+    }
+
+    fn reveal(state: &mut GameState, i: usize, amount: u64) -> Result<Vec<ChainEvent>> {
+        let salt = [i as u8; 32];
+        state.process_chain_action(
+            &player(i),
+            &ChainAction::RevealBid { amount, salt },
+            0,
+            None,
+        )
+    }
+
+    fn results(deltas: &[i32]) -> Vec<(Identity, i32)> {
+        deltas
+            .iter()
+            .enumerate()
+            .map(|(i, &delta)| (player(i), delta))
+            .collect()
+    }
+
+    #[test]
+    fn reveal_must_match_the_commitment() {
+        let mut state = auction(&[50, 50]);
+        commit(&mut state, 0, 20).unwrap();
+        // Copying someone else's commitment does not let you reveal their bid
+        let commitment = bid_commitment(20, &[0; 32], &player(0));
+        state
+            .process_chain_action(&player(1), &ChainAction::CommitBid { commitment }, 0, None)
+            .unwrap();
+        assert_eq!(state.auction.phase, AuctionPhase::Revealing);
+
+        let copied = ChainAction::RevealBid {
+            amount: 20,
+            salt: [0; 32],
+        };
+        assert!(state
+            .process_chain_action(&player(1), &copied, 0, None)
+            .is_err());
+        assert!(reveal(&mut state, 0, 21).is_err());
+        let wrong_salt = ChainAction::RevealBid {
+            amount: 20,
+            salt: [1; 32],
+        };
+        assert!(state
+            .process_chain_action(&player(0), &wrong_salt, 0, None)
+            .is_err());
+
+        reveal(&mut state, 0, 20).unwrap();
+        assert!(reveal(&mut state, 0, 20).is_err());
+    }
+
+    #[test]
+    fn bid_cannot_exceed_the_bet() {
+        let mut state = auction(&[50, 50]);
+        commit(&mut state, 0, 60).unwrap();
+        commit(&mut state, 1, 10).unwrap();
+        assert!(reveal(&mut state, 0, 60).is_err());
+    }
+
+    #[test]
+    fn non_revealers_lose_their_bet() {
+        let mut state = auction(&[50, 50, 50]);
+        commit(&mut state, 0, 20).unwrap();
+        commit(&mut state, 1, 10).unwrap();
+        commit(&mut state, 2, 30).unwrap();
+        reveal(&mut state, 0, 20).unwrap();
+        reveal(&mut state, 1, 10).unwrap();
+        // Still waiting on player2
+        assert_eq!(state.auction.phase, AuctionPhase::Revealing);
+
+        assert!(state
+            .process_chain_action(&player(0), &ChainAction::Close, 0, None)
+            .is_err());
+        state
+            .process_chain_action(&Identity::new("backend"), &ChainAction::Close, 0, None)
+            .unwrap();
+        assert_eq!(state.winner(), Some(&player(0)));
+        assert_eq!(state.get_end_results().unwrap(), results(&[80, -50, -50]));
+    }
+
+    #[test]
+    fn non_committers_lose_their_bet() {
+        let mut state = auction(&[50, 50, 50]);
+        commit(&mut state, 0, 20).unwrap();
+        commit(&mut state, 1, 10).unwrap();
+        assert!(state
+            .process_chain_action(&player(0), &ChainAction::StartReveal, 0, None)
+            .is_err());
+        state
+            .process_chain_action(
+                &Identity::new("backend"),
+                &ChainAction::StartReveal,
+                0,
+                None,
+            )
+            .unwrap();
+        assert!(commit(&mut state, 2, 30).is_err());
+
+        reveal(&mut state, 1, 10).unwrap();
+        reveal(&mut state, 0, 20).unwrap();
+        // Every committed bid is revealed, no need to wait for the backend
+        assert_eq!(state.auction.phase, AuctionPhase::Closed);
+        assert_eq!(state.get_end_results().unwrap(), results(&[80, -50, -50]));
+    }
+
+    #[test]
+    fn earliest_reveal_wins_ties() {
+        let mut state = auction(&[50, 50]);
+        commit(&mut state, 0, 20).unwrap();
+        commit(&mut state, 1, 20).unwrap();
+        reveal(&mut state, 1, 20).unwrap();
+        reveal(&mut state, 0, 20).unwrap();
+        assert_eq!(state.winner(), Some(&player(1)));
+        assert_eq!(state.get_end_results().unwrap(), results(&[-50, 30]));
+    }
+
+    #[test]
+    fn nobody_wins_without_reveals() {
+        let mut state = auction(&[50, 50]);
+        commit(&mut state, 0, 20).unwrap();
+        let backend = Identity::new("backend");
+        state
+            .process_chain_action(&backend, &ChainAction::StartReveal, 0, None)
+            .unwrap();
+        state
+            .process_chain_action(&backend, &ChainAction::Close, 0, None)
+            .unwrap();
+        assert_eq!(state.winner(), None);
+        assert_eq!(state.get_end_results().unwrap(), results(&[0, 0]));
+    }
+
+    #[test]
+    fn abort_only_after_timeout() {
+        let mut state = auction(&[50, 50]);
+        commit(&mut state, 0, 20).unwrap();
+        state.last_interaction_time = 1_000;
+
+        let anyone = Identity::new("anyone");
+        assert!(state
+            .process_chain_action(&anyone, &ChainAction::Abort, ABORT_TIMEOUT_MS + 999, None)
+            .is_err());
+        let events = state
+            .process_chain_action(&anyone, &ChainAction::Abort, ABORT_TIMEOUT_MS + 1_000, None)
+            .unwrap();
+        assert!(matches!(
+            &events[..],
+            [ChainEvent::MinigameAborted { final_results }] if final_results == &results(&[0, 0])
+        ));
+        assert_eq!(state.auction.phase, AuctionPhase::Uninitialized);
+
+        // There is nothing left to abort
+        assert!(state
+            .process_chain_action(&anyone, &ChainAction::Abort, ABORT_TIMEOUT_MS + 1_000, None)
+            .is_err());
+    }
+
+    #[test]
+    fn closed_auctions_cannot_be_aborted() {
+        let mut state = auction(&[50, 50]);
+        commit(&mut state, 0, 20).unwrap();
+        commit(&mut state, 1, 10).unwrap();
+        reveal(&mut state, 0, 20).unwrap();
+        reveal(&mut state, 1, 10).unwrap();
+        assert_eq!(state.auction.phase, AuctionPhase::Closed);
+        assert!(state
+            .process_chain_action(
+                &Identity::new("anyone"),
+                &ChainAction::Abort,
+                ABORT_TIMEOUT_MS,
+                None
+            )
+            .is_err());
+    }
+}
@@ -0,0 +1,48 @@
+use core::fmt;
+
+use crate::ChainEvent;
+
+impl fmt::Display for ChainEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChainEvent::MinigameInitialized { player_count } => {
+                write!(f, "Auction initialized with {} players", player_count)
+            }
+            ChainEvent::BidCommitted { player_id } => {
+                write!(f, "Player {} committed a bid", player_id)
+            }
+            ChainEvent::RevealStarted => {
+                write!(f, "Bids are being revealed")
+            }
+            ChainEvent::BidRevealed { player_id, amount } => {
+                write!(f, "Player {} bid {}", player_id, amount)
+            }
+            ChainEvent::AuctionClosed {
+                winner,
+                winning_bid,
+                prize,
+            } => match winner {
+                Some(winner) => write!(
+                    f,
+                    "Player {} won {} with a bid of {}",
+                    winner, prize, winning_bid
+                ),
+                None => write!(f, "Auction closed without any bid"),
+            },
+            ChainEvent::MinigameEnded { final_results } => {
+                write!(
+                    f,
+                    "Minigame ended with {} player results",
+                    final_results.len()
+                )
+            }
+            ChainEvent::MinigameAborted { final_results } => {
+                write!(
+                    f,
+                    "Minigame aborted, {} players keep their bets",
+                    final_results.len()
+                )
+            }
+        }
+    }
+}
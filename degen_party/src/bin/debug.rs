@@ -33,6 +33,10 @@ pub struct Args {
 pub struct ContractsConf {
     pub board_game: String,
     pub crash_game: String,
+    pub sealed_auction: String,
+    pub reaction_duel: String,
+    pub deposit_token: String,
+    pub reward_token: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
@@ -139,6 +143,10 @@ async fn main() -> Result<()> {
         data_directory: config.data_directory.clone(),
         board_game: ContractName::new(config.contracts.board_game.clone()),
         crash_game: ContractName::new(config.contracts.crash_game.clone()),
+        sealed_auction: ContractName::new(config.contracts.sealed_auction.clone()),
+        reaction_duel: ContractName::new(config.contracts.reaction_duel.clone()),
+        deposit_token: ContractName::new(config.contracts.deposit_token.clone()),
+        reward_token: ContractName::new(config.contracts.reward_token.clone()),
     });
 
     tracing::info!("Setting up modules");
@@ -85,6 +85,8 @@ pub struct DebugAnalyzer {
     crypto: Arc<CryptoContext>,
     board_game: ContractName,
     crash_game: ContractName,
+    /// Token standalone crash rounds are played with.
+    deposit_token: ContractName,
     board_game_txs: BTreeMap<TransactionKey, (BlobTransaction, TxContext)>,
     crash_game_txs: BTreeMap<TransactionKey, (BlobTransaction, TxContext)>,
     tx_status: HashMap<TxId, TxStatus>,
@@ -102,6 +104,7 @@ impl Module for DebugAnalyzer {
             crypto: ctx.crypto.clone(),
             board_game: ctx.board_game.clone(),
             crash_game: ctx.crash_game.clone(),
+            deposit_token: ctx.deposit_token.clone(),
             board_game_txs: BTreeMap::new(),
             crash_game_txs: BTreeMap::new(),
             tx_status: HashMap::new(),
@@ -130,6 +133,7 @@ impl Module for DebugAnalyzer {
         let crash_game_state: ::crash_game::GameState = ::crash_game::GameState::new(
             self.board_game.clone(),
             Identity::new(format!("{}@secp256k1", self.crypto.public_key,)),
+            self.deposit_token.clone(),
         );
         let crash_game_executor = CrashGameExecutor {
             state: crash_game_state,
@@ -27,6 +27,10 @@ pub struct EnsureRegistration {
     board_game: ContractName,
     #[allow(unused)]
     crash_game: ContractName,
+    #[allow(unused)]
+    sealed_auction: ContractName,
+    #[allow(unused)]
+    reaction_duel: ContractName,
 }
 
 impl Module for EnsureRegistration {
@@ -41,42 +45,65 @@ impl Module for EnsureRegistration {
             hyle_client,
             board_game: ctx.board_game.clone(),
             crash_game: ctx.crash_game.clone(),
+            sealed_auction: ctx.sealed_auction.clone(),
+            reaction_duel: ctx.reaction_duel.clone(),
         };
 
-        let a = ctx.client.get_contract(ctx.board_game.clone()).await;
-        let b = ctx.client.get_contract(ctx.crash_game.clone()).await;
-
-        if let (Ok(_), Ok(_)) = (a, b) {
-            tracing::info!("Contracts already registered");
-            return Ok(module);
-        }
-
-        module
-            .register_contract(
+        let backend_identity = Identity::new(format!("{}@secp256k1", ctx.crypto.public_key));
+        let contracts = [
+            (
                 ctx.board_game.clone(),
-                board_game::game::GameState::new(Identity::new(format!(
-                    "{}@secp256k1",
-                    ctx.crypto.public_key
-                )))
-                .commit(),
-            )
-            .await?;
-        module
-            .register_contract(
+                board_game::game::GameState::new(backend_identity.clone()).commit(),
+            ),
+            (
                 ctx.crash_game.clone(),
                 crash_game::GameState::new(
                     ctx.board_game.clone(),
-                    Identity::new(format!("{}@secp256k1", ctx.crypto.public_key,)),
+                    backend_identity.clone(),
+                    ctx.deposit_token.clone(),
                 )
                 .commit(),
-            )
-            .await?;
+            ),
+            (
+                ctx.sealed_auction.clone(),
+                sealed_auction::GameState::new(ctx.board_game.clone(), backend_identity.clone())
+                    .commit(),
+            ),
+            (
+                ctx.reaction_duel.clone(),
+                reaction_duel::GameState::new(ctx.board_game.clone(), backend_identity.clone())
+                    .commit(),
+            ),
+        ];
+
+        // Contracts added since the last deployment are registered next to the existing ones.
+        let mut missing = vec![];
+        for (contract_name, state_commitment) in contracts {
+            if ctx
+                .client
+                .get_contract(contract_name.clone())
+                .await
+                .is_err()
+            {
+                missing.push(contract_name.clone());
+                module
+                    .register_contract(contract_name, state_commitment)
+                    .await?;
+            }
+        }
+
+        if missing.is_empty() {
+            tracing::info!("Contracts already registered");
+            return Ok(module);
+        }
 
         tokio::time::timeout(std::time::Duration::from_secs(60), async {
             loop {
-                let a = ctx.client.get_contract(ctx.board_game.clone()).await;
-                let b = ctx.client.get_contract(ctx.crash_game.clone()).await;
-                if let (Ok(_), Ok(_)) = (a, b) {
+                let mut registered = true;
+                for contract_name in &missing {
+                    registered &= ctx.client.get_contract(contract_name.clone()).await.is_ok();
+                }
+                if registered {
                     break;
                 }
                 tokio::time::sleep(std::time::Duration::from_millis(250)).await;
@@ -118,6 +145,10 @@ impl EnsureRegistration {
                         contracts::BOARD_GAME_ELF
                     } else if contract_name == self.crash_game {
                         contracts::CRASH_GAME_ELF
+                    } else if contract_name == self.sealed_auction {
+                        contracts::SEALED_AUCTION_ELF
+                    } else if contract_name == self.reaction_duel {
+                        contracts::REACTION_DUEL_ELF
                     } else {
                         bail!("Unknown contract name: {}", contract_name)
                     }
@@ -142,6 +173,10 @@ impl EnsureRegistration {
                             contracts::BOARD_GAME_ELF
                         } else if contract_name == self.crash_game {
                             contracts::CRASH_GAME_ELF
+                        } else if contract_name == self.sealed_auction {
+                            contracts::SEALED_AUCTION_ELF
+                        } else if contract_name == self.reaction_duel {
+                            contracts::REACTION_DUEL_ELF
                         } else {
                             bail!("Unknown contract name: {}", contract_name)
                         }
@@ -1,5 +1,6 @@
 use std::{path::PathBuf, sync::Arc};
 
+use board_game::game::GameConfig;
 use client_sdk::rest_client::{NodeApiClient, NodeApiHttpClient};
 use config::{Config, Environment};
 use hyle_modules::modules::websocket::WebSocketConfig;
@@ -29,6 +30,10 @@ pub struct Context {
     pub data_directory: PathBuf,
     pub board_game: ContractName,
     pub crash_game: ContractName,
+    pub sealed_auction: ContractName,
+    pub reaction_duel: ContractName,
+    pub deposit_token: ContractName,
+    pub reward_token: ContractName,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +50,11 @@ pub struct AuthenticatedMessage<T> {
 pub enum InboundWebsocketMessage {
     GameState(GameStateCommand),
     CrashGame(CrashGameCommand),
+    /// Command for any registered minigame, in the format that minigame expects.
+    Minigame {
+        contract_name: ContractName,
+        command: serde_json::Value,
+    },
 }
 
 /// Messages sent to WebSocket clients from the system
@@ -53,12 +63,23 @@ pub enum InboundWebsocketMessage {
 pub enum OutboundWebsocketMessage {
     GameStateEvent(GameStateEvent),
     CrashGame(CrashGameEvent),
+    /// Event of any registered minigame, in the format that minigame defines.
+    Minigame {
+        contract_name: ContractName,
+        event: serde_json::Value,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct ContractsConf {
     pub board_game: String,
     pub crash_game: String,
+    pub sealed_auction: String,
+    pub reaction_duel: String,
+    /// SMT token players deposit into the board game
+    pub deposit_token: String,
+    /// SMT token board game rewards are paid with
+    pub reward_token: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
@@ -79,6 +100,15 @@ pub struct Conf {
     pub node_api: String,
 
     pub contracts: ContractsConf,
+    /// Rules of the board games the server starts. The token contracts come from `contracts`.
+    #[serde(default)]
+    pub game_config: GameConfig,
+    /// Minigames the board game draws from, by contract name.
+    /// Only list the ones the frontend has a screen for.
+    pub board_minigames: Vec<String>,
+    /// Minigame played in the last round of every game, if any. It needs a frontend screen too.
+    #[serde(default)]
+    pub final_minigame: Option<String>,
 
     /// When running only the indexer, the address of the DA server to connect to
     pub da_read_from: String,
@@ -93,6 +93,10 @@ async fn main_fn() -> Result<()> {
         data_directory: config.data_directory.clone(),
         board_game: ContractName::new(config.contracts.board_game.clone()),
         crash_game: ContractName::new(config.contracts.crash_game.clone()),
+        sealed_auction: ContractName::new(config.contracts.sealed_auction.clone()),
+        reaction_duel: ContractName::new(config.contracts.reaction_duel.clone()),
+        deposit_token: ContractName::new(config.contracts.deposit_token.clone()),
+        reward_token: ContractName::new(config.contracts.reward_token.clone()),
     });
 
     tracing::info!("Setting up modules");
@@ -71,7 +71,9 @@ impl TxExecutorHandler for CrashGameExecutor {
             )
         {
             tracing::warn!("Received ChainActionBlob: {:?}", parameters);
-            if let ChainAction::InitMinigame { time, .. } = parameters.1 {
+            if let ChainAction::InitMinigame { time, .. } | ChainAction::OpenRound { time, .. } =
+                parameters.1
+            {
                 /*let mut state = GameState::new(
                     self.board_game.clone(),
                     Identity::new(format!("{}@secp256k1", self.crypto.public_key)),
@@ -82,6 +84,14 @@ impl TxExecutorHandler for CrashGameExecutor {
             } else if let ChainAction::Start { time } = parameters.1 {
                 self.state.minigame_backend.game_start_time = Some(time as u128);
                 self.state.minigame_backend.current_time = Some(time as u128);
+            } else if let (ChainAction::Join { .. }, Some(tx_ctx)) =
+                (parameters.1, &calldata.tx_ctx)
+            {
+                // Standalone rounds can stay open for a while: count down from the first join
+                if self.state.minigame_verifiable.players.len() == 1 {
+                    self.state.minigame_backend.game_setup_time = Some(tx_ctx.timestamp.0);
+                    self.state.minigame_backend.current_time = Some(tx_ctx.timestamp.0);
+                }
             }
         }
 
@@ -101,6 +111,50 @@ impl TxExecutorHandler for CrashGameExecutor {
         self.state.commit()
     }
 
+    fn construct_state(
+        _register_blob: &RegisterContractEffect,
+        metadata: &Option<Vec<u8>>,
+    ) -> anyhow::Result<Self> {
+        if let Some(metadata) = metadata {
+            let (board_contract, backend_identity, standalone_token) = borsh::from_slice(metadata)?;
+            Ok(Self {
+                state: crash_game::GameState::new(
+                    board_contract,
+                    backend_identity,
+                    standalone_token,
+                ),
+            })
+        } else {
+            anyhow::bail!("No metadata provided");
+        }
+    }
+}
+
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone)]
+pub struct SealedAuctionExecutor {
+    pub state: sealed_auction::GameState,
+}
+
+impl TxExecutorHandler for SealedAuctionExecutor {
+    fn handle(&mut self, calldata: &sdk::Calldata) -> Result<sdk::HyleOutput> {
+        let initial_state_commitment = self.state.commit();
+        let mut res = self.state.execute(calldata);
+        Ok(as_hyle_output(
+            initial_state_commitment,
+            self.state.commit(),
+            calldata,
+            &mut res,
+        ))
+    }
+
+    fn build_commitment_metadata(&self, _blob: &sdk::Blob) -> Result<Vec<u8>> {
+        Ok(self.state.commit().0)
+    }
+
+    fn get_state_commitment(&self) -> StateCommitment {
+        self.state.commit()
+    }
+
     fn construct_state(
         _register_blob: &RegisterContractEffect,
         metadata: &Option<Vec<u8>>,
@@ -108,7 +162,47 @@ impl TxExecutorHandler for CrashGameExecutor {
         if let Some(metadata) = metadata {
             let (board_contract, backend_identity) = borsh::from_slice(metadata)?;
             Ok(Self {
-                state: crash_game::GameState::new(board_contract, backend_identity),
+                state: sealed_auction::GameState::new(board_contract, backend_identity),
+            })
+        } else {
+            anyhow::bail!("No metadata provided");
+        }
+    }
+}
+
+#[derive(Debug, BorshSerialize, BorshDeserialize, Clone)]
+pub struct ReactionDuelExecutor {
+    pub state: reaction_duel::GameState,
+}
+
+impl TxExecutorHandler for ReactionDuelExecutor {
+    fn handle(&mut self, calldata: &sdk::Calldata) -> Result<sdk::HyleOutput> {
+        let initial_state_commitment = self.state.commit();
+        let mut res = self.state.execute(calldata);
+        Ok(as_hyle_output(
+            initial_state_commitment,
+            self.state.commit(),
+            calldata,
+            &mut res,
+        ))
+    }
+
+    fn build_commitment_metadata(&self, _blob: &sdk::Blob) -> Result<Vec<u8>> {
+        Ok(self.state.commit().0)
+    }
+
+    fn get_state_commitment(&self) -> StateCommitment {
+        self.state.commit()
+    }
+
+    fn construct_state(
+        _register_blob: &RegisterContractEffect,
+        metadata: &Option<Vec<u8>>,
+    ) -> anyhow::Result<Self> {
+        if let Some(metadata) = metadata {
+            let (board_contract, backend_identity) = borsh::from_slice(metadata)?;
+            Ok(Self {
+                state: reaction_duel::GameState::new(board_contract, backend_identity),
             })
         } else {
             anyhow::bail!("No metadata provided");
@@ -129,10 +223,23 @@ pub async fn setup_auto_provers(
     let crash_game_state: crash_game::GameState = crash_game::GameState::new(
         ctx.board_game.clone(),
         Identity::new(format!("{}@secp256k1", ctx.crypto.public_key,)),
+        ctx.deposit_token.clone(),
     );
     let crash_game_executor = CrashGameExecutor {
         state: crash_game_state,
     };
+    let sealed_auction_executor = SealedAuctionExecutor {
+        state: sealed_auction::GameState::new(
+            ctx.board_game.clone(),
+            Identity::new(format!("{}@secp256k1", ctx.crypto.public_key)),
+        ),
+    };
+    let reaction_duel_executor = ReactionDuelExecutor {
+        state: reaction_duel::GameState::new(
+            ctx.board_game.clone(),
+            Identity::new(format!("{}@secp256k1", ctx.crypto.public_key)),
+        ),
+    };
     #[cfg(not(feature = "fake_proofs"))]
     let board_game_prover = {
         let pk = load_pk(
@@ -190,6 +297,60 @@ pub async fn setup_auto_provers(
         }))
         .await?;
 
+    #[cfg(not(feature = "fake_proofs"))]
+    let sealed_auction_prover = {
+        let pk = load_pk(
+            contracts::SEALED_AUCTION_ELF,
+            &ctx.data_directory.join("sealed_auction_pk.json"),
+        );
+        Arc::new(client_sdk::helpers::sp1::SP1Prover::new(pk).await)
+    };
+    #[cfg(feature = "fake_proofs")]
+    let sealed_auction_prover = Arc::new(client_sdk::helpers::test::TxExecutorTestProver::<
+        sealed_auction::GameState,
+    >::new());
+
+    handler
+        .build_module::<AutoProver<SealedAuctionExecutor>>(Arc::new(AutoProverCtx {
+            data_directory: ctx.data_directory.clone(),
+            prover: sealed_auction_prover,
+            contract_name: ctx.sealed_auction.clone(),
+            node: ctx.client.clone(),
+            api: None,
+            default_state: sealed_auction_executor,
+            buffer_blocks: ctx.config.buffer_blocks,
+            max_txs_per_proof: ctx.config.max_txs_per_proof,
+            tx_working_window_size: ctx.config.tx_working_window_size,
+        }))
+        .await?;
+
+    #[cfg(not(feature = "fake_proofs"))]
+    let reaction_duel_prover = {
+        let pk = load_pk(
+            contracts::REACTION_DUEL_ELF,
+            &ctx.data_directory.join("reaction_duel_pk.json"),
+        );
+        Arc::new(client_sdk::helpers::sp1::SP1Prover::new(pk).await)
+    };
+    #[cfg(feature = "fake_proofs")]
+    let reaction_duel_prover = Arc::new(client_sdk::helpers::test::TxExecutorTestProver::<
+        reaction_duel::GameState,
+    >::new());
+
+    handler
+        .build_module::<AutoProver<ReactionDuelExecutor>>(Arc::new(AutoProverCtx {
+            data_directory: ctx.data_directory.clone(),
+            prover: reaction_duel_prover,
+            contract_name: ctx.reaction_duel.clone(),
+            node: ctx.client.clone(),
+            api: None,
+            default_state: reaction_duel_executor,
+            buffer_blocks: ctx.config.buffer_blocks,
+            max_txs_per_proof: ctx.config.max_txs_per_proof,
+            tx_working_window_size: ctx.config.tx_working_window_size,
+        }))
+        .await?;
+
     Ok(())
 }
 
@@ -1,12 +1,11 @@
-use ::crash_game::ChainEvent;
 use anyhow::Context as _;
 use anyhow::Result;
-use board_game::game::GameEvent;
+use board_game::game::{ActionError, GameConfig, GameEvent};
 use borsh::{BorshDeserialize, BorshSerialize};
 use client_sdk::light_executor::LightContractExecutor;
 use client_sdk::light_executor::LightExecutorOutput;
 use client_sdk::transaction_builder::TxExecutorHandler;
-use crash_game::CrashGameEvent;
+use crash_game::CrashGameMinigame;
 use futures::FutureExt;
 use game_state::GameStateEvent;
 use hyle_modules::utils::native_verifier_handler::NativeVerifierHandler;
@@ -18,11 +17,14 @@ use hyle_modules::{
         Module, ModulesHandler,
     },
 };
+use minigame::Minigame;
+use reaction_duel::ReactionDuelMinigame;
 use sdk::BlobIndex;
 use sdk::{
     hyle_model_utils::TimestampMs, BlobTransaction, BlockHeight, Calldata, ContractName, Hashed,
     Identity, LaneId, MempoolStatusEvent, NodeStateEvent, TransactionData, TxContext, TxHash, TxId,
 };
+use sealed_auction::SealedAuctionMinigame;
 use smt_token::client::light_executor::LightSmtExecutor;
 use std::fmt;
 use std::{
@@ -44,19 +46,32 @@ use wallet::client::light_executor::LightWalletExecutor;
 
 use crate::{
     fake_lane_manager::ConfirmedBlobTransaction,
-    proving::{BoardGameExecutor, CrashGameExecutor},
+    proving::{BoardGameExecutor, CrashGameExecutor, ReactionDuelExecutor, SealedAuctionExecutor},
     AuthenticatedMessage, Context, CryptoContext, InboundWebsocketMessage,
     OutboundWebsocketMessage,
 };
 
 pub mod crash_game;
 pub mod game_state;
+pub mod minigame;
+pub mod reaction_duel;
+pub mod sealed_auction;
 
 pub struct RollupExecutor {
     bus: RollupExecutorBusClient,
     data_directory: PathBuf,
     crypto: Arc<CryptoContext>,
     store: RollupExecutorStore,
+    /// Rules of the games the backend initializes, whatever the player asking for it sends.
+    game_config: GameConfig,
+    /// Server side of every minigame the board game can start, by contract name.
+    minigames: BTreeMap<ContractName, Box<dyn Minigame>>,
+    /// Minigames put in the rotation of the games the backend initializes.
+    board_minigames: Vec<ContractName>,
+    /// Minigame of the last round of the games the backend initializes.
+    final_minigame: Option<ContractName>,
+    /// When the backend last sent its seed reveal.
+    last_seed_reveal: Option<Instant>,
     // Convenience, TODO refactor this ?
     last_claim_reward: Instant,
 }
@@ -87,6 +102,8 @@ pub trait MarkerExec: TxExecutorHandler {}
 impl MarkerExec for NativeVerifierHandler {}
 impl MarkerExec for BoardGameExecutor {}
 impl MarkerExec for CrashGameExecutor {}
+impl MarkerExec for SealedAuctionExecutor {}
+impl MarkerExec for ReactionDuelExecutor {}
 
 impl<T: MarkerExec> RollupExecWrapper for T {
     fn handle(
@@ -323,7 +340,19 @@ impl Module for RollupExecutor {
         );
 
         let mut store = match Self::load_from_disk::<DeserRollupExecutorStore>(file.as_path()) {
-            Some(store) => RollupExecutorStore::deser_with(store, ctx.contract_deserializer),
+            Some(store) => {
+                let mut store = RollupExecutorStore::deser_with(store, ctx.contract_deserializer);
+                // Contracts added since the store was saved start from their initial state.
+                for (contract_name, contract) in ctx.initial_contracts {
+                    if !store.settled_state.contains_key(&contract_name) {
+                        store
+                            .contracts
+                            .insert(contract_name.clone(), contract.clone());
+                        store.settled_state.insert(contract_name, contract);
+                    }
+                }
+                store
+            }
             None => RollupExecutorStore {
                 contracts: ctx.initial_contracts.clone(),
                 settled_state: ctx.initial_contracts,
@@ -338,11 +367,63 @@ impl Module for RollupExecutor {
         // Even when deserializing, we set the catching up to height.
         store.catching_up_to = catching_up_to;
 
+        let game_config = GameConfig {
+            deposit_token: ctx.common.deposit_token.clone(),
+            reward_token: ctx.common.reward_token.clone(),
+            ..ctx.common.config.game_config.clone()
+        };
+
+        let minigames: BTreeMap<ContractName, Box<dyn Minigame>> = BTreeMap::from([
+            (
+                ctx.common.crash_game.clone(),
+                Box::new(CrashGameMinigame::default()) as Box<dyn Minigame>,
+            ),
+            (
+                ctx.common.sealed_auction.clone(),
+                Box::new(SealedAuctionMinigame::default()) as Box<dyn Minigame>,
+            ),
+            (
+                ctx.common.reaction_duel.clone(),
+                Box::new(ReactionDuelMinigame::default()) as Box<dyn Minigame>,
+            ),
+        ]);
+        let board_minigames: Vec<ContractName> = ctx
+            .common
+            .config
+            .board_minigames
+            .iter()
+            .map(|name| ContractName::new(name.clone()))
+            .collect();
+        if board_minigames.is_empty() {
+            anyhow::bail!("board_minigames cannot be empty");
+        }
+        for minigame in &board_minigames {
+            if !minigames.contains_key(minigame) {
+                anyhow::bail!("No server support for board minigame {}", minigame);
+            }
+        }
+        let final_minigame = ctx
+            .common
+            .config
+            .final_minigame
+            .clone()
+            .map(ContractName::new);
+        if let Some(minigame) = &final_minigame {
+            if !minigames.contains_key(minigame) {
+                anyhow::bail!("No server support for final minigame {}", minigame);
+            }
+        }
+
         Ok(RollupExecutor {
             bus,
             store,
             data_directory,
             crypto: ctx.common.crypto.clone(),
+            game_config,
+            minigames,
+            board_minigames,
+            final_minigame,
+            last_seed_reveal: None,
             last_claim_reward: Instant::now(),
         })
     }
@@ -378,10 +459,19 @@ impl Module for RollupExecutor {
                 } = msg.message;
                 if let InboundWebsocketMessage::GameState(event) = message {
                     if let Err(e) = self.handle_user_message(event, identity, &uuid, identity_blobs).await {
-                        tracing::warn!("Error handling event: {:?}", e);
+                        if let Some(ActionError::DuplicateAction { player_id, uuid }) = e.downcast_ref::<ActionError>() {
+                            tracing::info!("Ignoring duplicate action {} from {}", uuid, player_id);
+                        } else {
+                            tracing::warn!("Error handling event: {:?}", e);
+                        }
+                    }
+                 } else if let InboundWebsocketMessage::CrashGame(command) = message {
+                    let crash_game = self.crash_game.clone();
+                    if let Err(e) = self.handle_minigame_command(&crash_game, serde_json::json!(command), identity, &uuid, identity_blobs) {
+                        tracing::warn!("Error handling player message: {:?}", e);
                     }
-                 } else if let InboundWebsocketMessage::CrashGame(event) = message {
-                    if let Err(e) = self.handle_player_message(event, identity, &uuid, identity_blobs).await {
+                } else if let InboundWebsocketMessage::Minigame { contract_name, command } = message {
+                    if let Err(e) = self.handle_minigame_command(&contract_name, command, identity, &uuid, identity_blobs) {
                         tracing::warn!("Error handling player message: {:?}", e);
                     }
                 }
@@ -411,13 +501,7 @@ impl Module for RollupExecutor {
                             },
                         ),
                     })?;
-                    let state = Some(self.get_crash_game().clone());
-                    self.bus.send(WsBroadcastMessage {
-                        message: OutboundWebsocketMessage::CrashGame(CrashGameEvent::StateUpdated {
-                            state,
-                            events: vec![],
-                        }),
-                    })?;
+                    self.broadcast_minigame_states()?;
                 } else if let Some(Err(e)) = res {
                     tracing::error!("Error in reprocessing task: {:?}", e);
                 }
@@ -425,7 +509,7 @@ impl Module for RollupExecutor {
             }
             _ = update_interval.tick() => {
                 _ = log_error!(self.board_game_on_tick().await, "board game on tick");
-                _ = log_error!(self.crash_game_on_tick().await, "crash game on tick");
+                _ = log_error!(self.minigames_on_tick(), "minigames on tick");
             }
         };
 
@@ -612,15 +696,11 @@ impl RollupExecutor {
                         },
                     ),
                 })?;
-            } else if contract_name == &self.crash_game {
-                let events: Vec<ChainEvent> = borsh::from_slice(&output).unwrap();
-                let state = Some(self.get_crash_game().clone());
-                self.bus.send(WsBroadcastMessage {
-                    message: OutboundWebsocketMessage::CrashGame(CrashGameEvent::StateUpdated {
-                        state,
-                        events,
-                    }),
+            } else if self.minigames.contains_key(contract_name) {
+                let message = self.with_minigame(contract_name, |minigame, ctx| {
+                    minigame.decode_events(ctx, output)
                 })?;
+                self.bus.send(WsBroadcastMessage { message })?;
             }
         }
 
@@ -870,12 +950,29 @@ pub async fn setup_rollup_execution(
     let crash_game_state: ::crash_game::GameState = ::crash_game::GameState::new(
         ctx.board_game.clone(),
         Identity::new(format!("{}@secp256k1", ctx.crypto.public_key,)),
+        ctx.deposit_token.clone(),
     );
     let crash_game_executor = CrashGameExecutor {
         state: crash_game_state,
     };
+    let sealed_auction_executor = SealedAuctionExecutor {
+        state: ::sealed_auction::GameState::new(
+            ctx.board_game.clone(),
+            Identity::new(format!("{}@secp256k1", ctx.crypto.public_key)),
+        ),
+    };
     let board_game = ctx.board_game.clone();
     let crash_game = ctx.crash_game.clone();
+    let reaction_duel_executor = ReactionDuelExecutor {
+        state: ::reaction_duel::GameState::new(
+            ctx.board_game.clone(),
+            Identity::new(format!("{}@secp256k1", ctx.crypto.public_key)),
+        ),
+    };
+    let sealed_auction = ctx.sealed_auction.clone();
+    let reaction_duel = ctx.reaction_duel.clone();
+    let deposit_token = ctx.deposit_token.clone();
+    let reward_token = ctx.reward_token.clone();
     handler
         .build_module::<RollupExecutor>(RollupExecutorCtx {
             common: ctx.clone(),
@@ -889,11 +986,19 @@ pub async fn setup_rollup_execution(
                     ContractBox::new(crash_game_executor.clone()),
                 ),
                 (
-                    ContractName::new("oxygen"),
+                    ctx.sealed_auction.clone(),
+                    ContractBox::new(sealed_auction_executor.clone()),
+                ),
+                (
+                    ctx.reaction_duel.clone(),
+                    ContractBox::new(reaction_duel_executor.clone()),
+                ),
+                (
+                    ctx.reward_token.clone(),
                     ContractBox::new(LightSmtExecutor::default()),
                 ),
                 (
-                    ContractName::new("oranj"),
+                    ctx.deposit_token.clone(),
                     ContractBox::new(LightSmtExecutor::default()),
                 ),
                 (
@@ -920,9 +1025,17 @@ pub async fn setup_rollup_execution(
                     ContractBox::new(
                         borsh::from_slice::<CrashGameExecutor>(&data).expect("Bad serialized data"),
                     )
-                } else if contract_name == &ContractName::new("oranj")
-                    || contract_name == &ContractName::new("oxygen")
-                {
+                } else if contract_name == &sealed_auction {
+                    ContractBox::new(
+                        borsh::from_slice::<SealedAuctionExecutor>(&data)
+                            .expect("Bad serialized data"),
+                    )
+                } else if contract_name == &reaction_duel {
+                    ContractBox::new(
+                        borsh::from_slice::<ReactionDuelExecutor>(&data)
+                            .expect("Bad serialized data"),
+                    )
+                } else if contract_name == &deposit_token || contract_name == &reward_token {
                     ContractBox::new(
                         borsh::from_slice::<LightSmtExecutor>(&data).expect("Bad serialized data"),
                     )
@@ -1,24 +1,18 @@
 use anyhow::{bail, Result};
-use board_game::{
-    game::{MinigameResult, PlayerMinigameResult},
-    GameActionBlob,
-};
+use board_game::game::MinigameSetup;
 use crash_game::{
-    ChainAction, ChainActionBlob, ChainEvent, GameState, MinigameState, ServerAction,
+    crash_commitment, crash_point, ChainAction, ChainActionBlob, ChainEvent, GameState,
+    MinigameState, Multiplier, SeedChain, ServerAction, ServerEvent,
 };
-use hyle_modules::bus::BusClientSender;
-use hyle_modules::modules::websocket::WsBroadcastMessage;
-use rand;
-use sdk::verifiers::Secp256k1Blob;
 use sdk::{Blob, BlobIndex, BlobTransaction, ContractAction, Identity};
-use secp256k1::Message;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::ops::DerefMut;
-use std::time::{SystemTime, UNIX_EPOCH};
+use smt_token::SmtTokenAction;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tracing::info;
 use uuid;
 
+use super::minigame::{Minigame, MinigameContext};
 use crate::{proving::CrashGameExecutor, OutboundWebsocketMessage};
 
 // Message types
@@ -26,7 +20,21 @@ use crate::{proving::CrashGameExecutor, OutboundWebsocketMessage};
 #[serde(tag = "type", content = "payload")]
 pub enum CrashGameCommand {
     SendState,
-    CashOut { player_id: Identity },
+    CashOut {
+        player_id: Identity,
+    },
+    SetAutoCashOut {
+        player_id: Identity,
+        target: Option<Multiplier>,
+    },
+    /// Joins the open standalone round, transferring the bet to the contract.
+    Join {
+        player_id: Identity,
+        name: String,
+        bet: u64,
+    },
+    /// Ends a round the backend stopped moving, see `ABORT_TIMEOUT_MS`.
+    Abort,
     End,
 }
 
@@ -37,160 +45,396 @@ pub enum CrashGameEvent {
         state: Option<GameState>,
         events: Vec<ChainEvent>,
     },
+    /// A command was refused before reaching the chain, e.g. a bet out of the limits.
+    ServerEvent { event: ServerEvent },
+}
+
+/// Rounds covered by each seed chain the backend commits.
+const SEED_CHAIN_LENGTH: u64 = 10_000;
+/// How long to wait for a backend transaction to land before sending it again.
+const RESEND_DELAY: Duration = Duration::from_secs(5);
+
+/// Server side of the crash game: starts and crashes rounds, and relays cash outs.
+/// While the board is idle, it also runs standalone rounds back to back.
+#[derive(Default)]
+pub(crate) struct CrashGameMinigame {
+    /// Seed of the current round, for the seed chain it was derived for.
+    seed: Option<(SeedChain, [u8; 32])>,
+    /// When the backend last sent a transaction moving the round on.
+    last_sent: Option<Instant>,
+}
+
+fn crash_state(ctx: &mut MinigameContext) -> Result<&mut GameState> {
+    Ok(&mut ctx.executor::<CrashGameExecutor>()?.state)
 }
 
-impl super::RollupExecutor {
-    pub(super) fn get_crash_game(&mut self) -> &mut GameState {
-        let bg = self.crash_game.clone();
-        &mut self
-            .contracts
-            .get_mut(&bg)
-            .expect("Crash game not initialized")
-            .deref_mut()
-            .as_any_mut()
-            .downcast_mut::<CrashGameExecutor>()
-            .expect("Crash game state is not of the expected type")
-            .state
+/// Link of the seed chain of `generation`, `depth` hashes away from its root. The root
+/// derives from the backend key, so the seeds survive a restart but nobody else knows them.
+fn seed_chain_link(ctx: &MinigameContext, generation: u64, depth: u64) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(b"crash_seed_chain");
+    hasher.update(ctx.crypto.secret_key.secret_bytes());
+    hasher.update(generation.to_le_bytes());
+    let mut link: [u8; 32] = hasher.finalize().into();
+    for _ in 0..depth {
+        link = crash_commitment(&link);
     }
+    link
+}
 
-    pub(super) async fn handle_player_message(
-        &mut self,
-        event: CrashGameCommand,
-        identity: Identity,
-        uuid: &str,
-        identity_blobs: Vec<Blob>,
-    ) -> Result<()> {
-        let uuid_128: u128 = uuid::Uuid::parse_str(uuid)?.as_u128();
-        let mut blobs = match event {
-            CrashGameCommand::SendState => {
-                let state = self.get_crash_game().clone();
-                return self.broadcast_state_update(state, vec![]);
+fn server_event(event: ServerEvent) -> OutboundWebsocketMessage {
+    OutboundWebsocketMessage::CrashGame(CrashGameEvent::ServerEvent { event })
+}
+
+/// Checks a bet against the contract limits, telling the player when it is refused.
+fn check_bet(ctx: &mut MinigameContext, player_id: &Identity, bet: u64) -> Result<bool> {
+    let limits = crash_state(ctx)?.limits;
+    if limits.check_bet(bet).is_ok() {
+        return Ok(true);
+    }
+    ctx.broadcast(server_event(ServerEvent::InvalidBetAmount {
+        player_id: player_id.clone(),
+        min: limits.min_bet,
+        max: limits.max_bet,
+        provided: bet,
+    }));
+    Ok(false)
+}
+
+fn state_updated(state: GameState, events: Vec<ChainEvent>) -> OutboundWebsocketMessage {
+    OutboundWebsocketMessage::CrashGame(CrashGameEvent::StateUpdated {
+        state: Some(state),
+        events,
+    })
+}
+
+// Server-side state management
+fn create_crash_backend_tx(ctx: &MinigameContext, action: ChainAction) -> Result<BlobTransaction> {
+    let uuid = uuid::Uuid::new_v4();
+    let identity_blob = ctx.backend_identity_blob(
+        uuid,
+        match action {
+            ChainAction::OpenRound { .. } => "OpenRound",
+            ChainAction::Start { .. } => "Start",
+            ChainAction::Crash { .. } => "Crash",
+            ChainAction::CommitSeedChain { .. } => "CommitSeedChain",
+            _ => unreachable!(),
+        },
+    )?;
+    Ok(BlobTransaction::new(
+        ctx.backend_identity(),
+        vec![
+            identity_blob,
+            ChainActionBlob(uuid.as_u128(), action).as_blob(ctx.contract_name.clone(), None, None),
+        ],
+    ))
+}
+
+/// Ends a standalone round: the settle blob, then a transfer to each winner.
+fn settle_blobs(ctx: &mut MinigameContext, uuid_128: u128) -> Result<Vec<Blob>> {
+    let state = crash_state(ctx)?;
+    let token = state.standalone_token.clone();
+    let payouts = state.payouts()?;
+    let mut blobs = vec![ChainActionBlob(uuid_128, ChainAction::Settle).as_blob(
+        ctx.contract_name.clone(),
+        None,
+        None,
+    )];
+    for (recipient, amount) in payouts {
+        blobs.push(
+            SmtTokenAction::Transfer {
+                sender: Identity::new(ctx.contract_name.0.clone()),
+                recipient,
+                amount: amount as u128,
             }
-            CrashGameCommand::CashOut { player_id } => {
-                self.handle_cash_out(uuid_128, player_id).await
+            .as_blob(token.clone(), None, None),
+        );
+    }
+    Ok(blobs)
+}
+
+/// Aborts a stalled round: every player gets their bet back, through the board or by transfer.
+fn abort_blobs(ctx: &mut MinigameContext, uuid_128: u128) -> Result<Vec<Blob>> {
+    let state = crash_state(ctx)?;
+    if !matches!(
+        state.minigame_verifiable.state,
+        MinigameState::WaitingForStart | MinigameState::Running
+    ) {
+        bail!("No round in progress to abort");
+    }
+
+    if state.minigame_verifiable.standalone {
+        let token = state.standalone_token.clone();
+        let refunds = state.refunds();
+        let mut blobs = vec![ChainActionBlob(uuid_128, ChainAction::Abort).as_blob(
+            ctx.contract_name.clone(),
+            None,
+            None,
+        )];
+        for (recipient, amount) in refunds {
+            blobs.push(
+                SmtTokenAction::Transfer {
+                    sender: Identity::new(ctx.contract_name.0.clone()),
+                    recipient,
+                    amount: amount as u128,
+                }
+                .as_blob(token.clone(), None, None),
+            );
+        }
+        return Ok(blobs);
+    }
+
+    let final_results = state.aborted_results();
+    Ok(board_end_blobs(
+        ctx,
+        uuid_128,
+        ChainAction::Abort,
+        final_results,
+    ))
+}
+
+/// `action` ending the minigame, calling the board `EndMinigame` with the results.
+fn board_end_blobs(
+    ctx: &MinigameContext,
+    uuid_128: u128,
+    action: ChainAction,
+    final_results: Vec<(Identity, i32)>,
+) -> Vec<Blob> {
+    vec![
+        ChainActionBlob(uuid_128, action).as_blob(
+            ctx.contract_name.clone(),
+            None,
+            Some(vec![BlobIndex(1)]),
+        ),
+        ctx.end_minigame_blob(uuid_128, &final_results),
+    ]
+}
+
+impl CrashGameMinigame {
+    /// Seed of the current round: the next one to reveal in the committed chain.
+    fn current_seed(&mut self, ctx: &mut MinigameContext) -> Result<[u8; 32]> {
+        let chain = crash_state(ctx)?.seed_chain;
+        if let Some((cached_chain, seed)) = self.seed {
+            if cached_chain == chain {
+                return Ok(seed);
             }
-            CrashGameCommand::End => self.handle_end(uuid_128).await,
-        }?;
-        // Merge blobs with identity blobs
-        blobs.extend(identity_blobs);
-        let tx = BlobTransaction::new(identity, blobs);
-        self.bus.send(tx)?;
-        Ok(())
+        }
+        if chain.remaining == 0 {
+            bail!("No crash seed left, a new seed chain is needed");
+        }
+        let seed = seed_chain_link(ctx, chain.generation, chain.remaining - 1);
+        if crash_commitment(&seed) != chain.head {
+            bail!("The committed seed chain was not derived from this backend key");
+        }
+        self.seed = Some((chain, seed));
+        Ok(seed)
     }
 
-    // Pre-chain validation and transaction submission
-    async fn handle_cash_out(&mut self, uuid_128: u128, player_id: Identity) -> Result<Vec<Blob>> {
-        // Pre-chain validation
-        let multiplier = self.get_crash_game().minigame_backend.current_multiplier;
+    fn send(&mut self, ctx: &mut MinigameContext, tx: BlobTransaction) {
+        ctx.send(tx);
+        self.last_sent = Some(Instant::now());
+    }
+}
 
-        Ok(vec![ChainActionBlob(
+impl Minigame for CrashGameMinigame {
+    fn init_blob(
+        &mut self,
+        ctx: &mut MinigameContext,
+        uuid_128: u128,
+        players: MinigameSetup,
+    ) -> Result<Blob> {
+        // The board takes over an open standalone round only once nobody is in it
+        let state = crash_state(ctx)?;
+        if state.minigame_verifiable.standalone && !state.minigame_verifiable.players.is_empty() {
+            bail!("A standalone crash round is still being played, try again once it is over");
+        }
+        // The round commits to the next seed of the chain, make sure there is one
+        self.current_seed(ctx)?;
+        Ok(ChainActionBlob(
             uuid_128,
-            ChainAction::CashOut {
-                player_id,
-                multiplier,
+            ChainAction::InitMinigame {
+                players,
+                time: SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64,
             },
         )
-        .as_blob(self.crash_game.clone(), None, None)])
+        .as_blob(ctx.contract_name.clone(), None, Some(vec![BlobIndex(0)])))
+    }
+
+    fn handle_command(
+        &mut self,
+        ctx: &mut MinigameContext,
+        command: serde_json::Value,
+        uuid_128: u128,
+    ) -> Result<Vec<Blob>> {
+        match serde_json::from_value(command)? {
+            CrashGameCommand::SendState => {
+                let message = self.state_message(ctx)?;
+                ctx.broadcast(message);
+                Ok(vec![])
+            }
+            CrashGameCommand::CashOut { player_id } => {
+                // The contract checks the multiplier against the time of the transaction,
+                // so compute it from the recorded start rather than the last tick.
+                let Some(started_at) = crash_state(ctx)?.minigame_verifiable.started_at else {
+                    bail!("Game is not running");
+                };
+                let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+                let multiplier =
+                    GameState::calculate_multiplier(now.saturating_sub(started_at) as u64);
+                // Past the payout cap the contract refuses the cash out, the crash is coming
+                let state = crash_state(ctx)?;
+                let round_cap = state.round_cap();
+                if let Some(player) = state.minigame_verifiable.players.get(&player_id) {
+                    let cashed_out_at = player.auto_cash_out.unwrap_or(multiplier).min(multiplier);
+                    if cashed_out_at > round_cap {
+                        let event = ServerEvent::InsufficientFunds {
+                            player_id,
+                            available: round_cap.apply(player.bet)?,
+                            requested: cashed_out_at.apply(player.bet)?,
+                        };
+                        ctx.broadcast(server_event(event));
+                        return Ok(vec![]);
+                    }
+                }
+                Ok(vec![ChainActionBlob(
+                    uuid_128,
+                    ChainAction::CashOut {
+                        player_id,
+                        multiplier,
+                    },
+                )
+                .as_blob(ctx.contract_name.clone(), None, None)])
+            }
+            CrashGameCommand::SetAutoCashOut { player_id, target } => Ok(vec![ChainActionBlob(
+                uuid_128,
+                ChainAction::SetAutoCashOut { player_id, target },
+            )
+            .as_blob(ctx.contract_name.clone(), None, None)]),
+            CrashGameCommand::Join {
+                player_id,
+                name,
+                bet,
+            } => {
+                // The board is about to need the crash game, the round would be closed anyway
+                if !ctx.board_idle {
+                    bail!("A board game is in progress, standalone rounds are closed");
+                }
+                if !check_bet(ctx, &player_id, bet)? {
+                    return Ok(vec![]);
+                }
+                let token = crash_state(ctx)?.standalone_token.clone();
+                Ok(vec![
+                    ChainActionBlob(uuid_128, ChainAction::Join { name, bet }).as_blob(
+                        ctx.contract_name.clone(),
+                        None,
+                        None,
+                    ),
+                    SmtTokenAction::Transfer {
+                        sender: player_id,
+                        recipient: Identity::new(ctx.contract_name.0.clone()),
+                        amount: bet as u128,
+                    }
+                    .as_blob(token, None, None),
+                ])
+            }
+            CrashGameCommand::Abort => abort_blobs(ctx, uuid_128),
+            CrashGameCommand::End => self.end_blobs(ctx, uuid_128),
+        }
     }
 
-    async fn handle_end(&mut self, uuid_128: u128) -> Result<Vec<Blob>> {
+    fn end_blobs(&mut self, ctx: &mut MinigameContext, uuid_128: u128) -> Result<Vec<Blob>> {
         // Pre-chain validation
-        if self.get_crash_game().minigame_verifiable.state != MinigameState::Crashed {
+        if crash_state(ctx)?.minigame_verifiable.state != MinigameState::Crashed {
             bail!("Game is still running");
         }
 
-        // Get end results from server-side state
-        let final_results = self.get_crash_game().get_end_results()?;
-
-        Ok(vec![
-            ChainActionBlob(uuid_128, ChainAction::Done).as_blob(
-                self.crash_game.clone(),
-                None,
-                Some(vec![BlobIndex(1)]),
-            ),
-            GameActionBlob(
-                uuid_128,
-                board_game::game::GameAction::EndMinigame {
-                    result: MinigameResult {
-                        contract_name: self.crash_game.clone(),
-                        player_results: final_results
-                            .iter()
-                            .map(|r| PlayerMinigameResult {
-                                player_id: r.0.clone(),
-                                coins_delta: r.1,
-                            })
-                            .collect(),
-                    },
-                },
-            )
-            .as_blob(self.board_game.clone(), Some(BlobIndex(0)), None),
-        ])
-    }
+        if crash_state(ctx)?.minigame_verifiable.standalone {
+            return settle_blobs(ctx, uuid_128);
+        }
 
-    fn create_backend_identity_blob(&self, uuid: uuid::Uuid, data_to_sign: &str) -> Result<Blob> {
-        let identity = Identity::new(format!("{}@secp256k1", self.crypto.public_key));
-        let data = format!("{}:{}", uuid, data_to_sign).as_bytes().to_vec();
-        let mut hasher = Sha256::new();
-        hasher.update(data.clone());
-        let message_hash: [u8; 32] = hasher.finalize().into();
-        let signature = self
-            .crypto
-            .secp
-            .sign_ecdsa(Message::from_digest(message_hash), &self.crypto.secret_key);
-        Ok(Secp256k1Blob::new(
-            identity,
-            &data,
-            &self.crypto.public_key.to_string(),
-            &signature.to_string(),
-        )?
-        .as_blob())
-    }
+        // Get end results from server-side state
+        let final_results = crash_state(ctx)?.get_end_results()?;
 
-    // Server-side state management
-    fn create_crash_backend_tx(&self, action: ChainAction) -> Result<BlobTransaction> {
-        let uuid = uuid::Uuid::new_v4();
-        let identity = Identity::new(format!("{}@secp256k1", self.crypto.public_key));
-        let identity_blob = self.create_backend_identity_blob(
-            uuid,
-            match action {
-                ChainAction::Start { .. } => "Start",
-                ChainAction::Crash { .. } => "Crash",
-                _ => unreachable!(),
-            },
-        )?;
-        Ok(BlobTransaction::new(
-            identity.clone(),
-            vec![
-                identity_blob,
-                ChainActionBlob(uuid.as_u128(), action).as_blob(
-                    self.crash_game.clone(),
-                    None,
-                    None,
-                ),
-            ],
+        Ok(board_end_blobs(
+            ctx,
+            uuid_128,
+            ChainAction::Done,
+            final_results,
         ))
     }
 
-    pub(super) async fn crash_game_on_tick(&mut self) -> Result<()> {
-        let state = self.get_crash_game();
+    fn on_tick(&mut self, ctx: &mut MinigameContext) -> Result<()> {
+        let board_idle = ctx.board_idle;
+        // Don't send the same transaction every tick while waiting for it to land
+        let can_send = !self
+            .last_sent
+            .is_some_and(|sent| sent.elapsed() < RESEND_DELAY);
+        let state = crash_state(ctx)?;
 
-        if state.minigame_verifiable.state == MinigameState::WaitingForStart {
+        if state.minigame_verifiable.state == MinigameState::Uninitialized {
+            if !can_send {
+                return Ok(());
+            }
+            // Commit the seeds of the next rounds once the current chain is used up
+            if state.seed_chain.remaining == 0 {
+                let generation = state.seed_chain.generation + 1;
+                let tx = create_crash_backend_tx(
+                    ctx,
+                    ChainAction::CommitSeedChain {
+                        head: seed_chain_link(ctx, generation, SEED_CHAIN_LENGTH),
+                        length: SEED_CHAIN_LENGTH,
+                    },
+                )?;
+                self.send(ctx, tx);
+                return Ok(());
+            }
+            // Keep standalone rounds going while nobody is playing on the board
+            if board_idle {
+                let tx = create_crash_backend_tx(
+                    ctx,
+                    ChainAction::OpenRound {
+                        time: SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64,
+                    },
+                )?;
+                self.send(ctx, tx);
+            }
+            return Ok(());
+        } else if state.minigame_verifiable.state == MinigameState::WaitingForStart {
+            // Standalone rounds wait for someone to join
+            if state.minigame_verifiable.players.is_empty() || !can_send {
+                return Ok(());
+            }
             // After a while start
             let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
             if now.saturating_sub(state.minigame_backend.game_setup_time.unwrap()) > 10_000 {
-                self.bus
-                    .send(self.create_crash_backend_tx(ChainAction::Start { time: now as u64 })?)?;
+                let tx = create_crash_backend_tx(ctx, ChainAction::Start { time: now as u64 })?;
+                self.send(ctx, tx);
                 return Ok(());
             }
         } else if state.minigame_verifiable.state == MinigameState::Crashed {
-            // Auto-end the game after a while to unstuck players
+            if !can_send {
+                return Ok(());
+            }
             let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+            if state.minigame_verifiable.standalone {
+                // Pay the winners shortly after the crash, then the next round opens
+                let crashed_at = state.minigame_verifiable.crashed_at.unwrap_or(now);
+                if now.saturating_sub(crashed_at) > 5_000 {
+                    let uuid = uuid::Uuid::new_v4();
+                    let mut blobs = settle_blobs(ctx, uuid.as_u128())?;
+                    blobs.push(ctx.backend_identity_blob(uuid, "Settle")?);
+                    let tx = BlobTransaction::new(ctx.backend_identity(), blobs);
+                    self.send(ctx, tx);
+                }
+                return Ok(());
+            }
+            // Auto-end the game after a while to unstuck players
             if now.saturating_sub(state.minigame_backend.game_start_time.unwrap()) > 60_000 {
                 let uuid = uuid::Uuid::new_v4();
-                let identity = Identity::new(format!("{}@secp256k1", self.crypto.public_key));
-                let mut blobs = self.handle_end(uuid.as_u128()).await?;
-                blobs.push(self.create_backend_identity_blob(uuid, "EndMinigame")?);
-                self.bus.send(BlobTransaction::new(identity, blobs))?;
+                let mut blobs = self.end_blobs(ctx, uuid.as_u128())?;
+                blobs.push(ctx.backend_identity_blob(uuid, "EndMinigame")?);
+                let tx = BlobTransaction::new(ctx.backend_identity(), blobs);
+                self.send(ctx, tx);
                 return Ok(());
             }
             return Ok(());
@@ -201,48 +445,54 @@ impl super::RollupExecutor {
         }
 
         let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
-        let delta = now.saturating_sub(state.minigame_backend.current_time.unwrap());
         state.minigame_backend.current_time = Some(now);
-        let elapsed_ms = now.saturating_sub(state.minigame_backend.game_start_time.unwrap());
+        // Time the multiplier from the start the contract recorded, like it does.
+        let started_at = state
+            .minigame_verifiable
+            .started_at
+            .or(state.minigame_backend.game_start_time)
+            .unwrap_or(now);
+        let elapsed_ms = now.saturating_sub(started_at);
 
         // We don't actually send server events for now.
         let _events = state.process_server_action(ServerAction::Update {
             current_time: elapsed_ms as u64,
         })?;
 
-        // Crash probability calculation: from 1% to 50% after 8 seconds (and staying there)
-        let elapsed_secs = elapsed_ms as f64 / 1000.0;
-        let crash_probability = 0.01 + (0.49 * (elapsed_secs / 8.0).min(1.0));
-
-        // Instant probability over delta ms
-        let crash_probability = crash_probability * (delta as f64 / 1000.0);
-
-        info!(
-            "Updating game state - {}, {}",
-            elapsed_ms, crash_probability
-        );
+        info!("Updating game state - {}", elapsed_ms);
 
         let state = state.clone();
 
-        if rand::random::<f64>() < crash_probability {
-            self.bus
-                .send(self.create_crash_backend_tx(ChainAction::Crash {
-                    final_multiplier: state.minigame_backend.current_multiplier,
-                })?)?;
+        // Crash once the multiplier reaches the point committed to when the game was set up,
+        // or earlier if the round reached its payout cap.
+        // Until the contract sees the crash, keep sending it.
+        let seed = self.current_seed(ctx)?;
+        let final_multiplier = crash_point(&seed).min(state.round_cap());
+        if can_send && state.minigame_backend.current_multiplier >= final_multiplier {
+            let tx = create_crash_backend_tx(
+                ctx,
+                ChainAction::Crash {
+                    final_multiplier,
+                    seed,
+                },
+            )?;
+            self.send(ctx, tx);
         }
 
-        self.broadcast_state_update(state, vec![])?;
+        ctx.broadcast(state_updated(state, vec![]));
         Ok(())
     }
 
-    // Helper methods
-    fn broadcast_state_update(&mut self, state: GameState, events: Vec<ChainEvent>) -> Result<()> {
-        self.bus.send(WsBroadcastMessage {
-            message: OutboundWebsocketMessage::CrashGame(CrashGameEvent::StateUpdated {
-                state: Some(state),
-                events,
-            }),
-        })?;
-        Ok(())
+    fn decode_events(
+        &mut self,
+        ctx: &mut MinigameContext,
+        output: &[u8],
+    ) -> Result<OutboundWebsocketMessage> {
+        let events: Vec<ChainEvent> = borsh::from_slice(output)?;
+        Ok(state_updated(crash_state(ctx)?.clone(), events))
+    }
+
+    fn state_message(&mut self, ctx: &mut MinigameContext) -> Result<OutboundWebsocketMessage> {
+        Ok(state_updated(crash_state(ctx)?.clone(), vec![]))
     }
 }
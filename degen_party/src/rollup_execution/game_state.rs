@@ -1,9 +1,8 @@
 use anyhow::{bail, Result};
 use board_game::{
-    game::{GameAction as BoardGameAction, GameEvent, GamePhase, GameState},
+    game::{seed, GameAction as BoardGameAction, GameEvent, GamePhase, GameState},
     GameActionBlob,
 };
-use crash_game::ChainActionBlob;
 use hyle_modules::{bus::BusClientSender, modules::websocket::WsBroadcastMessage};
 use sdk::{
     verifiers::Secp256k1Blob, Blob, BlobIndex, BlobTransaction, ContractAction, ContractName,
@@ -82,6 +81,10 @@ impl super::RollupExecutor {
 
         tracing::warn!("Handling action: {:?}", action);
 
+        // The contract would reject it anyway, don't bother sending the transaction.
+        self.get_board_game()
+            .check_action_uuid(&identity, uuid_128)?;
+
         match &action {
             BoardGameAction::EndMinigame { result: _ } => {
                 bail!("EndMinigame cannot be called directly");
@@ -90,17 +93,16 @@ impl super::RollupExecutor {
                 match &self.get_board_game().phase {
                     GamePhase::StartMinigame(minigame_type)
                     | GamePhase::FinalMinigame(minigame_type) => {
-                        if minigame_type != &self.crash_game {
-                            bail!("Not the right minigame");
-                        }
+                        let minigame_type = minigame_type.clone();
                         tracing::warn!("Starting minigame: {:?}", minigame_type);
+                        let players = self.get_board_game().get_minigame_setup();
                         // TODO ensure we are synchronized correctly.
                         blobs.push(
                             GameActionBlob(
                                 uuid_128,
                                 BoardGameAction::StartMinigame {
                                     minigame: minigame_type.clone(),
-                                    players: self.get_board_game().get_minigame_setup(),
+                                    players: players.clone(),
                                 },
                             )
                             .as_blob(
@@ -109,21 +111,9 @@ impl super::RollupExecutor {
                                 None,
                             ),
                         );
-                        blobs.push(
-                            ChainActionBlob(
-                                uuid_128,
-                                crash_game::ChainAction::InitMinigame {
-                                    players: self.get_board_game().get_minigame_setup(),
-                                    time: SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis()
-                                        as u64,
-                                },
-                            )
-                            .as_blob(
-                                self.crash_game.clone(),
-                                None,
-                                Some(vec![BlobIndex(0)]),
-                            ),
-                        );
+                        blobs.push(self.with_minigame(&minigame_type, |minigame, ctx| {
+                            minigame.init_blob(ctx, uuid_128, players)
+                        })?);
                     }
                     _ => {
                         bail!("Not ready to start a game");
@@ -136,16 +126,25 @@ impl super::RollupExecutor {
                 return Ok(());
             }
             BoardGameAction::Initialize { .. } => {
-                blobs.push(
-                    GameActionBlob(
-                        uuid_128,
-                        BoardGameAction::Initialize {
-                            minigames: vec![self.crash_game.clone().0],
-                            random_seed: uuid_128 as u64,
-                        },
-                    )
-                    .as_blob(self.board_game.clone(), None, None),
-                );
+                // Commit to a secret of our own, revealed once registration is closed.
+                // We pick the seed ourselves: the secret is derived from it, and a reused seed
+                // would commit to a secret revealed in an earlier game.
+                let random_seed: u64 = rand::random();
+                let secret = self.seed_secret(random_seed);
+                // Only the backend can initialize a game, the player just asks for it:
+                // the rules are the server's, not the ones in the request.
+                let tx = self.create_backend_tx(BoardGameAction::Initialize {
+                    minigames: self.board_minigames.iter().map(|c| c.0.clone()).collect(),
+                    final_minigame: self.final_minigame.as_ref().map(|c| c.0.clone()),
+                    config: self.game_config.clone(),
+                    random_seed,
+                    seed_commitment: Some(seed::commitment(
+                        &secret,
+                        &self.get_board_game().backend_identity,
+                    )),
+                })?;
+                self.bus.send(tx)?;
+                return Ok(());
             }
             BoardGameAction::RegisterPlayer { deposit, .. } => {
                 blobs.push(
@@ -154,7 +153,11 @@ impl super::RollupExecutor {
                         recipient: Identity::new(self.board_game.clone().0),
                         amount: *deposit as u128,
                     }
-                    .as_blob(ContractName::new("oranj"), None, None),
+                    .as_blob(
+                        self.get_board_game().config.deposit_token.clone(),
+                        None,
+                        None,
+                    ),
                 );
                 blobs.push(GameActionBlob(uuid_128, action.clone()).as_blob(
                     self.board_game.clone(),
@@ -162,6 +165,27 @@ impl super::RollupExecutor {
                     None,
                 ));
             }
+            BoardGameAction::Leave => {
+                if let Some(refund) = self.get_board_game().leave_refund(&identity) {
+                    blobs.push(
+                        SmtTokenAction::Transfer {
+                            sender: Identity::new(self.board_game.clone().0),
+                            recipient: identity.clone(),
+                            amount: refund as u128,
+                        }
+                        .as_blob(
+                            self.get_board_game().config.deposit_token.clone(),
+                            None,
+                            None,
+                        ),
+                    );
+                }
+                blobs.push(GameActionBlob(uuid_128, action.clone()).as_blob(
+                    self.board_game.clone(),
+                    None,
+                    None,
+                ));
+            }
             _ => {
                 blobs.push(GameActionBlob(uuid_128, action.clone()).as_blob(
                     self.board_game.clone(),
@@ -191,8 +215,11 @@ impl super::RollupExecutor {
             uuid,
             match action {
                 BoardGameAction::EndGame => "EndGame",
+                BoardGameAction::Initialize { .. } => "Initialize",
                 BoardGameAction::SpinWheel => "SpinWheel",
                 BoardGameAction::DistributeRewards => "DistributeRewards",
+                BoardGameAction::RefundDeposits => "RefundDeposits",
+                BoardGameAction::RevealSeed { .. } => "RevealSeed",
                 _ => unreachable!(),
             }
         )
@@ -220,11 +247,40 @@ impl super::RollupExecutor {
         ))
     }
 
+    /// Secret behind the backend's seed commitment for the game initialized with `random_seed`.
+    /// Derived from our key rather than kept in memory, so a restart can still reveal it.
+    fn seed_secret(&self, random_seed: u64) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(b"board_seed_secret");
+        hasher.update(self.crypto.secret_key.secret_bytes());
+        hasher.update(random_seed.to_le_bytes());
+        hasher.finalize().into()
+    }
+
     pub(super) async fn board_game_on_tick(&mut self) -> Result<()> {
         let state = self.get_board_game();
         if state.phase == GamePhase::Betting {
+            let must_reveal = state
+                .seed_commitments
+                .get(&state.backend_identity)
+                .is_some_and(|c| c.secret.is_none());
+            if must_reveal {
+                // Still the `Initialize` seed, the dice are reseeded once every secret is in.
+                let secret = self.seed_secret(state.dice.seed);
+                // The game is aborted if we never reveal, so keep trying until it lands.
+                if self
+                    .last_seed_reveal
+                    .is_some_and(|sent| sent.elapsed().as_secs() < 5)
+                {
+                    return Ok(());
+                }
+                self.last_seed_reveal = Some(tokio::time::Instant::now());
+                let tx = self.create_backend_tx(BoardGameAction::RevealSeed { secret })?;
+                self.bus.send(tx)?;
+                return Ok(());
+            }
             let likely_timed_out = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis()
-                > state.round_started_at + 40 * 1000;
+                > state.round_started_at + state.config.spin_timeout_ms as u128;
             if likely_timed_out {
                 let tx = self.create_backend_tx(BoardGameAction::SpinWheel)?;
                 self.bus.send(tx)?;
@@ -235,14 +291,41 @@ impl super::RollupExecutor {
                 self.last_claim_reward = tokio::time::Instant::now();
                 let tx = self.create_backend_tx(BoardGameAction::DistributeRewards)?;
                 let mut blobs = tx.blobs.clone();
-                for player in &self.get_board_game().players {
+                for (recipient, amount) in self.get_board_game().reward_transfers() {
+                    blobs.push(
+                        SmtTokenAction::Transfer {
+                            sender: Identity::new(self.board_game.clone().0),
+                            recipient,
+                            amount: amount as u128,
+                        }
+                        .as_blob(
+                            self.get_board_game().config.reward_token.clone(),
+                            None,
+                            None,
+                        ),
+                    );
+                }
+                self.bus
+                    .send(BlobTransaction::new(tx.identity.clone(), blobs))?;
+            }
+        } else if state.phase == GamePhase::Refunding {
+            // avoid spam
+            if self.last_claim_reward.elapsed().as_secs() > 10 {
+                self.last_claim_reward = tokio::time::Instant::now();
+                let tx = self.create_backend_tx(BoardGameAction::RefundDeposits)?;
+                let mut blobs = tx.blobs.clone();
+                for (recipient, amount) in self.get_board_game().refund_transfers() {
                     blobs.push(
                         SmtTokenAction::Transfer {
                             sender: Identity::new(self.board_game.clone().0),
-                            recipient: player.id.clone(),
-                            amount: player.coins as u128,
+                            recipient,
+                            amount: amount as u128,
                         }
-                        .as_blob(ContractName::new("oxygen"), None, None),
+                        .as_blob(
+                            self.get_board_game().config.deposit_token.clone(),
+                            None,
+                            None,
+                        ),
                     );
                 }
                 self.bus
@@ -0,0 +1,210 @@
+use anyhow::{anyhow, Result};
+use board_game::{
+    game::{GameAction, GamePhase, MinigameResult, MinigameSetup, PlayerMinigameResult},
+    GameActionBlob,
+};
+use hyle_modules::{bus::BusClientSender, log_error, modules::websocket::WsBroadcastMessage};
+use sdk::{
+    verifiers::Secp256k1Blob, Blob, BlobIndex, BlobTransaction, ContractAction, ContractName,
+    Identity,
+};
+use secp256k1::Message;
+use sha2::{Digest, Sha256};
+
+use super::ContractBox;
+use crate::{CryptoContext, OutboundWebsocketMessage};
+
+/// Server side of a minigame played from the board game.
+/// Implementations are registered in `RollupExecutor::minigames`, keyed by contract name,
+/// and only talk to the rest of the server through the `MinigameContext`.
+pub(crate) trait Minigame: Send + Sync {
+    /// Blob starting the minigame, sent along the board's `StartMinigame` blob (at index 0).
+    fn init_blob(
+        &mut self,
+        ctx: &mut MinigameContext,
+        uuid: u128,
+        players: MinigameSetup,
+    ) -> Result<Blob>;
+
+    /// Blobs to send on behalf of a player, along with their identity blobs.
+    fn handle_command(
+        &mut self,
+        ctx: &mut MinigameContext,
+        command: serde_json::Value,
+        uuid: u128,
+    ) -> Result<Vec<Blob>>;
+
+    /// Blobs ending the minigame, including the board's `EndMinigame` blob.
+    fn end_blobs(&mut self, ctx: &mut MinigameContext, uuid: u128) -> Result<Vec<Blob>>;
+
+    /// Called on every tick of the executor to drive the minigame.
+    fn on_tick(&mut self, ctx: &mut MinigameContext) -> Result<()>;
+
+    /// Message for clients from the output of a transaction on the minigame contract.
+    fn decode_events(
+        &mut self,
+        ctx: &mut MinigameContext,
+        output: &[u8],
+    ) -> Result<OutboundWebsocketMessage>;
+
+    /// Message for clients with the current state of the minigame.
+    fn state_message(&mut self, ctx: &mut MinigameContext) -> Result<OutboundWebsocketMessage>;
+}
+
+/// What a minigame can access while handling a call. Transactions and messages are
+/// sent by the executor once the call returns.
+pub(crate) struct MinigameContext<'a> {
+    pub contract_name: &'a ContractName,
+    pub board_game: &'a ContractName,
+    /// No board game is being played, so minigames can run on their own.
+    pub board_idle: bool,
+    pub contract: &'a mut ContractBox,
+    pub crypto: &'a CryptoContext,
+    pub txs: Vec<BlobTransaction>,
+    pub broadcasts: Vec<OutboundWebsocketMessage>,
+}
+
+impl MinigameContext<'_> {
+    /// State of the minigame contract, as registered in the executor.
+    pub fn executor<T: 'static>(&mut self) -> Result<&mut T> {
+        self.contract
+            .as_any_mut()
+            .downcast_mut::<T>()
+            .ok_or_else(|| anyhow!("{} state is not of the expected type", self.contract_name))
+    }
+
+    pub fn backend_identity(&self) -> Identity {
+        Identity::new(format!("{}@secp256k1", self.crypto.public_key))
+    }
+
+    /// Signs `"{uuid}:{data_to_sign}"` so the contract can check the backend sent the action.
+    pub fn backend_identity_blob(&self, uuid: uuid::Uuid, data_to_sign: &str) -> Result<Blob> {
+        let data = format!("{}:{}", uuid, data_to_sign).as_bytes().to_vec();
+        let mut hasher = Sha256::new();
+        hasher.update(data.clone());
+        let message_hash: [u8; 32] = hasher.finalize().into();
+        let signature = self
+            .crypto
+            .secp
+            .sign_ecdsa(Message::from_digest(message_hash), &self.crypto.secret_key);
+        Ok(Secp256k1Blob::new(
+            self.backend_identity(),
+            &data,
+            &self.crypto.public_key.to_string(),
+            &signature.to_string(),
+        )?
+        .as_blob())
+    }
+
+    /// The board's `EndMinigame` blob, called by the minigame blob ending it at index 0.
+    pub fn end_minigame_blob(&self, uuid_128: u128, final_results: &[(Identity, i32)]) -> Blob {
+        GameActionBlob(
+            uuid_128,
+            GameAction::EndMinigame {
+                result: MinigameResult {
+                    contract_name: self.contract_name.clone(),
+                    player_results: final_results
+                        .iter()
+                        .map(|r| PlayerMinigameResult {
+                            player_id: r.0.clone(),
+                            coins_delta: r.1,
+                        })
+                        .collect(),
+                },
+            },
+        )
+        .as_blob(self.board_game.clone(), Some(BlobIndex(0)), None)
+    }
+
+    pub fn send(&mut self, tx: BlobTransaction) {
+        self.txs.push(tx);
+    }
+
+    pub fn broadcast(&mut self, message: OutboundWebsocketMessage) {
+        self.broadcasts.push(message);
+    }
+}
+
+impl super::RollupExecutor {
+    /// Runs `f` on a registered minigame, then sends what it queued.
+    pub(super) fn with_minigame<T>(
+        &mut self,
+        contract_name: &ContractName,
+        f: impl FnOnce(&mut dyn Minigame, &mut MinigameContext) -> Result<T>,
+    ) -> Result<T> {
+        let board_idle = matches!(
+            self.get_board_game().phase,
+            GamePhase::GameOver | GamePhase::Registration
+        );
+        let minigame = self
+            .minigames
+            .get_mut(contract_name)
+            .ok_or_else(|| anyhow!("No server support for minigame {}", contract_name))?;
+        let contract = self
+            .store
+            .contracts
+            .get_mut(contract_name)
+            .ok_or_else(|| anyhow!("Minigame {} is not registered", contract_name))?;
+        let mut ctx = MinigameContext {
+            contract_name,
+            board_game: &self.store.board_game,
+            board_idle,
+            contract,
+            crypto: &self.crypto,
+            txs: vec![],
+            broadcasts: vec![],
+        };
+        let result = f(minigame.as_mut(), &mut ctx);
+        let MinigameContext {
+            txs, broadcasts, ..
+        } = ctx;
+        for tx in txs {
+            self.bus.send(tx)?;
+        }
+        for message in broadcasts {
+            self.bus.send(WsBroadcastMessage { message })?;
+        }
+        result
+    }
+
+    /// Forwards a player command to a minigame and sends the resulting transaction.
+    pub(super) fn handle_minigame_command(
+        &mut self,
+        contract_name: &ContractName,
+        command: serde_json::Value,
+        identity: Identity,
+        uuid: &str,
+        identity_blobs: Vec<Blob>,
+    ) -> Result<()> {
+        let uuid_128: u128 = uuid::Uuid::parse_str(uuid)?.as_u128();
+        let mut blobs = self.with_minigame(contract_name, |minigame, ctx| {
+            minigame.handle_command(ctx, command, uuid_128)
+        })?;
+        if blobs.is_empty() {
+            return Ok(());
+        }
+        blobs.extend(identity_blobs);
+        self.bus.send(BlobTransaction::new(identity, blobs))?;
+        Ok(())
+    }
+
+    pub(super) fn minigames_on_tick(&mut self) -> Result<()> {
+        let names: Vec<_> = self.minigames.keys().cloned().collect();
+        for name in names {
+            _ = log_error!(
+                self.with_minigame(&name, |minigame, ctx| minigame.on_tick(ctx)),
+                "minigame on tick"
+            );
+        }
+        Ok(())
+    }
+
+    pub(super) fn broadcast_minigame_states(&mut self) -> Result<()> {
+        let names: Vec<_> = self.minigames.keys().cloned().collect();
+        for name in names {
+            let message = self.with_minigame(&name, |minigame, ctx| minigame.state_message(ctx))?;
+            self.bus.send(WsBroadcastMessage { message })?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,255 @@
+use anyhow::{bail, Result};
+use board_game::game::MinigameSetup;
+use reaction_duel::{
+    go_commitment, ChainAction, ChainActionBlob, ChainEvent, DuelPhase, GameState,
+    ABORT_TIMEOUT_MS, MAX_GO_DELAY_MS, REACTION_WINDOW_MS,
+};
+use sdk::{Blob, BlobIndex, BlobTransaction, ContractAction, Identity};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use super::minigame::{Minigame, MinigameContext};
+use crate::{proving::ReactionDuelExecutor, OutboundWebsocketMessage};
+
+/// Shortest go delay the backend draws, so players can't just tap right away.
+const MIN_GO_DELAY_MS: u64 = 2_000;
+/// Margin on top of the reaction window, for taps still on their way when it ends.
+const REVEAL_MARGIN_MS: u128 = 500;
+/// How long the result stays on screen before the minigame ends.
+const RESULT_TIME: Duration = Duration::from_secs(5);
+/// How long to wait for a backend transaction to land before sending it again.
+const RESEND_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "payload")]
+pub enum ReactionDuelCommand {
+    SendState,
+    Tap,
+    /// Ends a duel the backend never revealed, see `ABORT_TIMEOUT_MS`.
+    Abort,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "payload")]
+pub enum ReactionDuelEvent {
+    StateUpdated {
+        state: Option<GameState>,
+        events: Vec<ChainEvent>,
+    },
+    /// The committed go time was reached: players should tap now.
+    /// Only the backend knows it until the reveal, so this is the only way clients learn it.
+    Go { go_at: u128 },
+}
+
+/// Server side of the reaction duel: signals "go" to the players, then reveals it.
+#[derive(Default)]
+pub(crate) struct ReactionDuelMinigame {
+    /// Go delay and salt of the current duel, committed when the minigame is initialized.
+    go: Option<(u64, [u8; 32])>,
+    /// The go signal of the current duel was sent to the players.
+    go_sent: bool,
+    /// Since when the duel result is revealed.
+    revealed_since: Option<Instant>,
+    /// When the backend last sent a transaction moving the duel on.
+    last_sent: Option<Instant>,
+}
+
+fn duel_state(ctx: &mut MinigameContext) -> Result<&mut GameState> {
+    Ok(&mut ctx.executor::<ReactionDuelExecutor>()?.state)
+}
+
+fn duel_event(ctx: &MinigameContext, event: ReactionDuelEvent) -> Result<OutboundWebsocketMessage> {
+    Ok(OutboundWebsocketMessage::Minigame {
+        contract_name: ctx.contract_name.clone(),
+        event: serde_json::to_value(event)?,
+    })
+}
+
+/// `action` ending the minigame, calling the board `EndMinigame` with the results.
+fn board_end_blobs(
+    ctx: &MinigameContext,
+    uuid_128: u128,
+    action: ChainAction,
+    final_results: Vec<(Identity, i32)>,
+) -> Vec<Blob> {
+    vec![
+        ChainActionBlob(uuid_128, action).as_blob(
+            ctx.contract_name.clone(),
+            None,
+            Some(vec![BlobIndex(1)]),
+        ),
+        ctx.end_minigame_blob(uuid_128, &final_results),
+    ]
+}
+
+/// Aborts a duel that was never revealed: both players keep their coins.
+fn abort_blobs(ctx: &mut MinigameContext, uuid_128: u128) -> Result<Vec<Blob>> {
+    let state = duel_state(ctx)?;
+    if state.duel.phase != DuelPhase::WaitingForGo {
+        bail!("No duel in progress to abort");
+    }
+    let final_results = state.aborted_results();
+    Ok(board_end_blobs(
+        ctx,
+        uuid_128,
+        ChainAction::Abort,
+        final_results,
+    ))
+}
+
+impl ReactionDuelMinigame {
+    fn send_backend_tx(
+        &mut self,
+        ctx: &mut MinigameContext,
+        uuid: uuid::Uuid,
+        mut blobs: Vec<Blob>,
+        label: &str,
+    ) -> Result<()> {
+        blobs.push(ctx.backend_identity_blob(uuid, label)?);
+        ctx.send(BlobTransaction::new(ctx.backend_identity(), blobs));
+        self.last_sent = Some(Instant::now());
+        Ok(())
+    }
+}
+
+impl Minigame for ReactionDuelMinigame {
+    fn init_blob(
+        &mut self,
+        ctx: &mut MinigameContext,
+        uuid_128: u128,
+        players: MinigameSetup,
+    ) -> Result<Blob> {
+        let delay_ms = rand::random_range(MIN_GO_DELAY_MS..=MAX_GO_DELAY_MS);
+        let salt: [u8; 32] = rand::random();
+        self.go = Some((delay_ms, salt));
+        self.go_sent = false;
+        Ok(ChainActionBlob(
+            uuid_128,
+            ChainAction::InitMinigame {
+                players,
+                go_commitment: go_commitment(delay_ms, &salt),
+            },
+        )
+        .as_blob(ctx.contract_name.clone(), None, Some(vec![BlobIndex(0)])))
+    }
+
+    fn handle_command(
+        &mut self,
+        ctx: &mut MinigameContext,
+        command: serde_json::Value,
+        uuid_128: u128,
+    ) -> Result<Vec<Blob>> {
+        match serde_json::from_value(command)? {
+            ReactionDuelCommand::SendState => {
+                let message = self.state_message(ctx)?;
+                ctx.broadcast(message);
+                Ok(vec![])
+            }
+            ReactionDuelCommand::Tap => Ok(vec![ChainActionBlob(uuid_128, ChainAction::Tap)
+                .as_blob(ctx.contract_name.clone(), None, None)]),
+            ReactionDuelCommand::Abort => abort_blobs(ctx, uuid_128),
+        }
+    }
+
+    fn end_blobs(&mut self, ctx: &mut MinigameContext, uuid_128: u128) -> Result<Vec<Blob>> {
+        let final_results = duel_state(ctx)?.get_end_results()?;
+        Ok(board_end_blobs(
+            ctx,
+            uuid_128,
+            ChainAction::Done,
+            final_results,
+        ))
+    }
+
+    fn on_tick(&mut self, ctx: &mut MinigameContext) -> Result<()> {
+        let state = duel_state(ctx)?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+        let phase = state.duel.phase.clone();
+        if phase != DuelPhase::Revealed {
+            self.revealed_since = None;
+        }
+
+        // Don't send the same transaction every tick while waiting for it to land
+        let can_send = !self
+            .last_sent
+            .is_some_and(|sent| sent.elapsed() < RESEND_DELAY);
+
+        match phase {
+            DuelPhase::Uninitialized => Ok(()),
+            DuelPhase::WaitingForGo => {
+                // Without the go time, lost if the server restarted, the duel can never be revealed.
+                // Abort it once the contract allows it, so the board can go on.
+                let Some((delay_ms, salt)) = self.go else {
+                    if can_send
+                        && now.saturating_sub(state.last_interaction_time) >= ABORT_TIMEOUT_MS
+                    {
+                        let uuid = uuid::Uuid::new_v4();
+                        let blobs = abort_blobs(ctx, uuid.as_u128())?;
+                        self.send_backend_tx(ctx, uuid, blobs, "Abort")?;
+                    }
+                    return Ok(());
+                };
+
+                let go_at = state.duel.started_at + delay_ms as u128;
+                let everyone_tapped = state.duel.players.values().all(|p| p.tapped_at.is_some());
+                if now >= go_at && !self.go_sent {
+                    self.go_sent = true;
+                    let message = duel_event(ctx, ReactionDuelEvent::Go { go_at })?;
+                    ctx.broadcast(message);
+                }
+
+                // Reveal once both tapped, or once the contract no longer lets them tap
+                if can_send
+                    && (everyone_tapped || now >= go_at + REACTION_WINDOW_MS + REVEAL_MARGIN_MS)
+                {
+                    let uuid = uuid::Uuid::new_v4();
+                    let blobs = vec![ChainActionBlob(
+                        uuid.as_u128(),
+                        ChainAction::RevealGo { delay_ms, salt },
+                    )
+                    .as_blob(ctx.contract_name.clone(), None, None)];
+                    self.send_backend_tx(ctx, uuid, blobs, "RevealGo")?;
+                }
+                Ok(())
+            }
+            DuelPhase::Revealed => {
+                // The go time is public now, the next duel needs a new one
+                self.go = None;
+                let revealed_since = *self.revealed_since.get_or_insert_with(Instant::now);
+                if can_send && revealed_since.elapsed() > RESULT_TIME {
+                    let uuid = uuid::Uuid::new_v4();
+                    let blobs = self.end_blobs(ctx, uuid.as_u128())?;
+                    self.send_backend_tx(ctx, uuid, blobs, "EndMinigame")?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn decode_events(
+        &mut self,
+        ctx: &mut MinigameContext,
+        output: &[u8],
+    ) -> Result<OutboundWebsocketMessage> {
+        let events: Vec<ChainEvent> = borsh::from_slice(output)?;
+        let state = duel_state(ctx)?.clone();
+        duel_event(
+            ctx,
+            ReactionDuelEvent::StateUpdated {
+                state: Some(state),
+                events,
+            },
+        )
+    }
+
+    fn state_message(&mut self, ctx: &mut MinigameContext) -> Result<OutboundWebsocketMessage> {
+        let state = duel_state(ctx)?.clone();
+        duel_event(
+            ctx,
+            ReactionDuelEvent::StateUpdated {
+                state: Some(state),
+                events: vec![],
+            },
+        )
+    }
+}
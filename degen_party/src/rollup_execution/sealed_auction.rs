@@ -0,0 +1,219 @@
+use anyhow::{bail, Result};
+use board_game::game::MinigameSetup;
+use sdk::{Blob, BlobIndex, BlobTransaction, ContractAction, Identity};
+use sealed_auction::{AuctionPhase, ChainAction, ChainActionBlob, ChainEvent, GameState};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use super::minigame::{Minigame, MinigameContext};
+use crate::{proving::SealedAuctionExecutor, OutboundWebsocketMessage};
+
+/// How long players have to commit a bid before the backend starts the reveal.
+const BIDDING_TIME: Duration = Duration::from_secs(30);
+/// How long players have to reveal their bid before the backend closes the auction.
+const REVEAL_TIME: Duration = Duration::from_secs(30);
+/// How long the result stays on screen before the minigame ends.
+const RESULT_TIME: Duration = Duration::from_secs(5);
+/// How long to wait for a backend transaction to land before sending it again.
+const RESEND_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "payload")]
+pub enum SealedAuctionCommand {
+    SendState,
+    /// Hidden bid, see `sealed_auction::bid_commitment`.
+    CommitBid {
+        commitment: [u8; 32],
+    },
+    RevealBid {
+        amount: u64,
+        salt: [u8; 32],
+    },
+    /// Ends an auction the backend stopped moving, see `ABORT_TIMEOUT_MS`.
+    Abort,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", content = "payload")]
+pub enum SealedAuctionEvent {
+    StateUpdated {
+        state: Option<GameState>,
+        events: Vec<ChainEvent>,
+    },
+}
+
+/// Server side of the sealed auction: ends each phase once players had their time.
+#[derive(Default)]
+pub(crate) struct SealedAuctionMinigame {
+    /// Phase of the auction on the last tick, and since when.
+    phase: Option<(AuctionPhase, Instant)>,
+    /// When the backend last sent a transaction moving the auction on.
+    last_sent: Option<Instant>,
+}
+
+fn auction_state(ctx: &mut MinigameContext) -> Result<&mut GameState> {
+    Ok(&mut ctx.executor::<SealedAuctionExecutor>()?.state)
+}
+
+fn state_updated(
+    ctx: &MinigameContext,
+    state: GameState,
+    events: Vec<ChainEvent>,
+) -> Result<OutboundWebsocketMessage> {
+    Ok(OutboundWebsocketMessage::Minigame {
+        contract_name: ctx.contract_name.clone(),
+        event: serde_json::to_value(SealedAuctionEvent::StateUpdated {
+            state: Some(state),
+            events,
+        })?,
+    })
+}
+
+/// `action` ending the minigame, calling the board `EndMinigame` with the results.
+fn board_end_blobs(
+    ctx: &MinigameContext,
+    uuid_128: u128,
+    action: ChainAction,
+    final_results: Vec<(Identity, i32)>,
+) -> Vec<Blob> {
+    vec![
+        ChainActionBlob(uuid_128, action).as_blob(
+            ctx.contract_name.clone(),
+            None,
+            Some(vec![BlobIndex(1)]),
+        ),
+        ctx.end_minigame_blob(uuid_128, &final_results),
+    ]
+}
+
+impl Minigame for SealedAuctionMinigame {
+    fn init_blob(
+        &mut self,
+        ctx: &mut MinigameContext,
+        uuid_128: u128,
+        players: MinigameSetup,
+    ) -> Result<Blob> {
+        Ok(
+            ChainActionBlob(uuid_128, ChainAction::InitMinigame { players }).as_blob(
+                ctx.contract_name.clone(),
+                None,
+                Some(vec![BlobIndex(0)]),
+            ),
+        )
+    }
+
+    fn handle_command(
+        &mut self,
+        ctx: &mut MinigameContext,
+        command: serde_json::Value,
+        uuid_128: u128,
+    ) -> Result<Vec<Blob>> {
+        let action = match serde_json::from_value(command)? {
+            SealedAuctionCommand::SendState => {
+                let message = self.state_message(ctx)?;
+                ctx.broadcast(message);
+                return Ok(vec![]);
+            }
+            SealedAuctionCommand::CommitBid { commitment } => ChainAction::CommitBid { commitment },
+            SealedAuctionCommand::RevealBid { amount, salt } => {
+                ChainAction::RevealBid { amount, salt }
+            }
+            SealedAuctionCommand::Abort => {
+                let state = auction_state(ctx)?;
+                if !matches!(
+                    state.auction.phase,
+                    AuctionPhase::Bidding | AuctionPhase::Revealing
+                ) {
+                    bail!("No auction in progress to abort");
+                }
+                let final_results = state.aborted_results();
+                return Ok(board_end_blobs(
+                    ctx,
+                    uuid_128,
+                    ChainAction::Abort,
+                    final_results,
+                ));
+            }
+        };
+        Ok(vec![ChainActionBlob(uuid_128, action).as_blob(
+            ctx.contract_name.clone(),
+            None,
+            None,
+        )])
+    }
+
+    fn end_blobs(&mut self, ctx: &mut MinigameContext, uuid_128: u128) -> Result<Vec<Blob>> {
+        let final_results = auction_state(ctx)?.get_end_results()?;
+        Ok(board_end_blobs(
+            ctx,
+            uuid_128,
+            ChainAction::Done,
+            final_results,
+        ))
+    }
+
+    fn on_tick(&mut self, ctx: &mut MinigameContext) -> Result<()> {
+        let phase = auction_state(ctx)?.auction.phase.clone();
+        let since = match &self.phase {
+            Some((last_phase, since)) if *last_phase == phase => *since,
+            _ => {
+                self.phase = Some((phase.clone(), Instant::now()));
+                self.last_sent = None;
+                return Ok(());
+            }
+        };
+
+        // Don't send the same transaction every tick while waiting for it to land
+        if self
+            .last_sent
+            .is_some_and(|sent| sent.elapsed() < RESEND_DELAY)
+        {
+            return Ok(());
+        }
+
+        let uuid = uuid::Uuid::new_v4();
+        let (mut blobs, label) = match phase {
+            AuctionPhase::Bidding if since.elapsed() > BIDDING_TIME => (
+                vec![
+                    ChainActionBlob(uuid.as_u128(), ChainAction::StartReveal).as_blob(
+                        ctx.contract_name.clone(),
+                        None,
+                        None,
+                    ),
+                ],
+                "StartReveal",
+            ),
+            AuctionPhase::Revealing if since.elapsed() > REVEAL_TIME => (
+                vec![ChainActionBlob(uuid.as_u128(), ChainAction::Close).as_blob(
+                    ctx.contract_name.clone(),
+                    None,
+                    None,
+                )],
+                "Close",
+            ),
+            AuctionPhase::Closed if since.elapsed() > RESULT_TIME => {
+                (self.end_blobs(ctx, uuid.as_u128())?, "EndMinigame")
+            }
+            _ => return Ok(()),
+        };
+        blobs.push(ctx.backend_identity_blob(uuid, label)?);
+        ctx.send(BlobTransaction::new(ctx.backend_identity(), blobs));
+        self.last_sent = Some(Instant::now());
+        Ok(())
+    }
+
+    fn decode_events(
+        &mut self,
+        ctx: &mut MinigameContext,
+        output: &[u8],
+    ) -> Result<OutboundWebsocketMessage> {
+        let events: Vec<ChainEvent> = borsh::from_slice(output)?;
+        let state = auction_state(ctx)?.clone();
+        state_updated(ctx, state, events)
+    }
+
+    fn state_message(&mut self, ctx: &mut MinigameContext) -> Result<OutboundWebsocketMessage> {
+        let state = auction_state(ctx)?.clone();
+        state_updated(ctx, state, vec![])
+    }
+}
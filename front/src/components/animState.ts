@@ -1,5 +1,5 @@
 import { computed, reactive, ref, watch, watchEffect } from "vue";
-import { boardGameService, gameState } from "../game_data/game_data";
+import { boardGameService, gameState, type WheelOutcome } from "../game_data/game_data";
 
 export const animState = reactive({
     timeInRound: 0,
@@ -7,12 +7,12 @@ export const animState = reactive({
     currentRoundIndex: -1, // index in eventHistory
     // Object: animation id -> timestamp when played for this round
     playedAnimations: {} as Record<string, number>,
-    eventHistory: {} as Record<number, { round: number; outcome: number }>,
+    eventHistory: {} as Record<number, { round: number; outcome: WheelOutcome | null }>,
 });
 
 // Helper: get current round object
 export const currentRoundEvents = computed(
-    () => animState.eventHistory[animState.currentRoundIndex] || { round: -1, outcome: -1 },
+    () => animState.eventHistory[animState.currentRoundIndex] || { round: -1, outcome: null },
 );
 
 const roundToDay = (round: number) => {
@@ -31,46 +31,67 @@ const roundToDay = (round: number) => {
 };
 
 export const roundOutcome = (round: number) => {
-    if (round === 9) {
+    // The last round always ends with the final minigame
+    const lastRound = (gameState.game?.config.rounds ?? 10) - 1;
+    if (round === lastRound) {
         return {
-            outcome: 4,
+            outcome: "Minigame",
             title: `On the ${roundToDay(round)} day, the city beckons!`,
             description:
                 "As the dust settles, the city of the future emerges from the storm.\nThere is only one... last... Game!",
         };
     }
     const outcome = animState.eventHistory[round]?.outcome;
-    if (outcome === undefined) {
+    if (!outcome) {
         return {
-            outcome: -1,
+            outcome: null,
             title: `On the ${roundToDay(round)} day, history forgets...`,
             description: "No event has been recorded for this round.",
         };
     }
-    if (outcome === 0) {
-        return {
-            outcome: 0,
-            title: `The ${roundToDay(round)} day, a quiet day`,
-            description: "Really, not much happened at all.",
-        };
-    } else if (outcome === 1) {
-        return {
-            outcome: 1,
-            title: `On the ${roundToDay(round)} day, Fumble !`,
-            description: "In the dust storm, all players fumble their bets and swap them randomly.",
-        };
-    } else if (outcome === 2) {
-        return {
-            outcome: 2,
-            title: `On the ${roundToDay(round)} day, ALL or NOTHING!`,
-            description: "The situation is dire ! All players are forced to bet all their money to survive.",
-        };
-    } else {
-        return {
-            outcome: 3,
-            title: `On the ${roundToDay(round)} day, something strange happened!`,
-            description: "It seems... We are gamers!",
-        };
+    switch (outcome) {
+        case "Nothing":
+            return {
+                outcome,
+                title: `The ${roundToDay(round)} day, a quiet day`,
+                description: "Really, not much happened at all.",
+            };
+        case "RandomPayout":
+            return {
+                outcome,
+                title: `On the ${roundToDay(round)} day, Fumble !`,
+                description: "In the dust storm, all players fumble their bets and swap them randomly.",
+            };
+        case "AllOrNothing":
+            return {
+                outcome,
+                title: `On the ${roundToDay(round)} day, ALL or NOTHING!`,
+                description: "The situation is dire ! All players are forced to bet all their money to survive.",
+            };
+        case "SwapCoins":
+            return {
+                outcome,
+                title: `On the ${roundToDay(round)} day, a great mix-up!`,
+                description: "Players woke up with someone else's purse.",
+            };
+        case "StealFromLeader":
+            return {
+                outcome,
+                title: `On the ${roundToDay(round)} day, a heist!`,
+                description: "Someone snuck into the leader's vault.",
+            };
+        case "Jackpot":
+            return {
+                outcome,
+                title: `On the ${roundToDay(round)} day, JACKPOT!`,
+                description: "One lucky player scooped up the whole pot.",
+            };
+        case "Minigame":
+            return {
+                outcome,
+                title: `On the ${roundToDay(round)} day, something strange happened!`,
+                description: "It seems... We are gamers!",
+            };
     }
 };
 
@@ -162,20 +183,20 @@ watch(
 
 // Wheel spin logic
 
-const wheelOptions = [
-    { label: "Quiet day", color: "#36C6FF", outcome: 0 },
-    { label: "Minigame", color: "#FF4D4D", outcome: 3 },
-    { label: "Fumble", color: "#00C49A", outcome: 1 },
-    { label: "Minigame", color: "#FF4D4D", outcome: 4 },
-    { label: "All or Nothing", color: "#FFB347", outcome: 2 },
-    //{ label: "Minigame", color: "#FF4D4D", outcome: 5 },
+// The wheel image only has five slices, newer outcomes land on the closest one.
+const wheelOptions: Array<{ label: string; color: string; outcomes: WheelOutcome[] }> = [
+    { label: "Quiet day", color: "#36C6FF", outcomes: ["Nothing", "Jackpot"] },
+    { label: "Minigame", color: "#FF4D4D", outcomes: ["Minigame"] },
+    { label: "Fumble", color: "#00C49A", outcomes: ["RandomPayout", "SwapCoins", "StealFromLeader"] },
+    { label: "Minigame", color: "#FF4D4D", outcomes: [] },
+    { label: "All or Nothing", color: "#FFB347", outcomes: ["AllOrNothing"] },
 ];
 
 const spinning = ref(false);
 export const spinAngle = ref(0); // in radians
 const targetAngle = ref(0); // in radians
 const spinDuration = 2; // seconds
-const lastOutcome = ref<number | null>(null);
+const lastOutcome = ref<WheelOutcome | null>(null);
 
 const animateSpin = () => {
     if (!spinning.value) return;
@@ -192,13 +213,13 @@ const animateSpin = () => {
     }
 };
 
-const startSpinAnimation = (outcome: number) => {
+const startSpinAnimation = (outcome: WheelOutcome) => {
     markAnimationPlayed("SpinWheel");
     // The wheel should land so that the outcome slice is at the top (pointer)
     const sliceAngle = (2 * Math.PI) / wheelOptions.length;
     // Add several full spins for effect
     const fullSpins = 3;
-    const outcomeIndex = wheelOptions.findIndex((option) => option.outcome === outcome);
+    const outcomeIndex = wheelOptions.findIndex((option) => option.outcomes.includes(outcome));
     const outcomeAngle = sliceAngle * outcomeIndex;
     targetAngle.value = fullSpins * 2 * Math.PI - outcomeAngle;
     spinning.value = true;
@@ -208,7 +229,7 @@ const startSpinAnimation = (outcome: number) => {
 
 watchEffect(() => {
     const outcome = currentRoundEvents.value?.outcome;
-    if (outcome === undefined || outcome === -1 || isAnimationPlayed("SpinWheel")) return;
+    if (!outcome || isAnimationPlayed("SpinWheel")) return;
     spinAngle.value = 0;
     startSpinAnimation(outcome);
 });
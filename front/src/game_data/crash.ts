@@ -18,6 +18,18 @@ export type CrashGameCommand =
           type: "CashOut";
           payload: { player_id: string };
       }
+    | {
+          type: "SetAutoCashOut";
+          payload: { player_id: string; target: number | null };
+      }
+    | {
+          type: "Join";
+          payload: { player_id: string; name: string; bet: number };
+      }
+    | {
+          type: "Abort";
+          payload: null;
+      }
     | {
           type: "Start";
           payload: null;
@@ -27,14 +39,36 @@ export type CrashGameCommand =
           payload: null;
       };
 
-export type CrashGameEvent = {
-    type: "StateUpdated";
-    payload: { state: CrashGameState | null; events: ChainEvent[] };
-};
+/** Sent when the server refuses a command because of the bet limits. */
+export type ServerEvent =
+    | { InsufficientFunds: { player_id: string; available: number; requested: number } }
+    | { InvalidBetAmount: { player_id: string; min: number; max: number; provided: number } };
+
+export type CrashGameEvent =
+    | {
+          type: "StateUpdated";
+          payload: { state: CrashGameState | null; events: ChainEvent[] };
+      }
+    | {
+          type: "ServerEvent";
+          payload: { event: ServerEvent };
+      };
+
+/** Multipliers are sent in basis points: 10000 is 1x. */
+export const MULTIPLIER_BPS = 10_000;
 
 export interface CrashGameMinigameVerifiableState {
     state: "Uninitialized" | "WaitingForStart" | "Running" | "Crashed";
-    players: Record<string, { id: string; name: string; bet: number; cashed_out_at?: number }>;
+    players: Record<
+        string,
+        { id: string; name: string; bet: number; cashed_out_at?: number; auto_cash_out?: number | null }
+    >;
+    started_at: number | null;
+    crashed_at: number | null;
+    crash_commitment: number[];
+    crash_seed: number[] | null;
+    /** Round played outside the board game, with bets paid in tokens. */
+    standalone: boolean;
 }
 
 export interface CrashGameMinigameBackendState {
@@ -44,14 +78,33 @@ export interface CrashGameMinigameBackendState {
     current_time: number | null;
 }
 
+export interface BetLimits {
+    min_bet: number;
+    max_bet: number;
+    max_multiplier: number;
+    max_exposure: number;
+}
+
+/** Crash seeds are committed in advance: each revealed seed hashes to the previous one. */
+export interface SeedChain {
+    generation: number;
+    head: number[];
+    remaining: number;
+}
+
 export interface CrashGameState {
     minigame_verifiable: CrashGameMinigameVerifiableState;
     minigame_backend: CrashGameMinigameBackendState;
+    limits: BetLimits;
+    seed_chain: SeedChain;
 }
 
 export const crashGameState = reactive({
     minigame_verifiable: null as CrashGameMinigameVerifiableState | null,
     minigame_backend: null as CrashGameMinigameBackendState | null,
+    limits: null as BetLimits | null,
+    /** Last command of the local player refused by the server. */
+    rejection: null as ServerEvent | null,
 });
 
 class CrashGameService extends BaseWebSocketService {
@@ -63,9 +116,16 @@ class CrashGameService extends BaseWebSocketService {
                 if (state) {
                     crashGameState.minigame_verifiable = state.minigame_verifiable;
                     crashGameState.minigame_backend = state.minigame_backend;
+                    crashGameState.limits = state.limits;
                 } else {
                     console.log("Crash game state cleared");
                 }
+            } else if (event.type === "ServerEvent") {
+                const serverEvent: ServerEvent = event.payload.event;
+                const details = Object.values(serverEvent)[0];
+                if (details.player_id === getLocalPlayerId()) {
+                    crashGameState.rejection = serverEvent;
+                }
             }
         }
     }
@@ -85,6 +145,55 @@ class CrashGameService extends BaseWebSocketService {
         );
     }
 
+    /** Target is a plain multiplier (e.g. 2 for 2x), or null to clear it. */
+    setAutoCashOut(target: number | null) {
+        this.send(
+            {
+                type: "CrashGame",
+                payload: {
+                    type: "SetAutoCashOut",
+                    payload: {
+                        player_id: getLocalPlayerId(),
+                        target: target === null ? null : Math.round(target * MULTIPLIER_BPS),
+                    },
+                },
+            },
+            "SetAutoCashOut",
+        );
+    }
+
+    /** Joins the open standalone round; the bet is transferred to the contract. */
+    join(name: string, bet: number) {
+        this.send(
+            {
+                type: "CrashGame",
+                payload: {
+                    type: "Join",
+                    payload: {
+                        player_id: getLocalPlayerId(),
+                        name,
+                        bet,
+                    },
+                },
+            },
+            "Join",
+        );
+    }
+
+    /** Ends a round the backend stopped moving: every player gets their bet back. */
+    abort() {
+        this.send(
+            {
+                type: "CrashGame",
+                payload: {
+                    type: "Abort",
+                    payload: null,
+                },
+            },
+            "Abort",
+        );
+    }
+
     sendState() {
         this.send(
             {
@@ -36,6 +36,7 @@ export type GamePhase =
     | { InMinigame: string }
     | "FinalMinigame"
     | "RewardsDistribution"
+    | "Refunding"
     | "GameOver";
 
 export type MinigameSetup = Array<[string, string, number]>;
@@ -48,9 +49,11 @@ export type GameAction =
               random_seed: number;
           };
       }
-    | { RegisterPlayer: { name: string; deposit: number } }
+    | { RegisterPlayer: { name: string; deposit: number; seed_commitment?: number[] } }
+    | { RevealSeed: { secret: number[] } }
     | { StartGame: null }
     | { PlaceBet: { amount: number } }
+    | { Leave: null }
     | { SpinWheel: null }
     | { StartMinigame: { minigame: string; players: MinigameSetup } }
     | { EndMinigame: { result: MinigameResult } }
@@ -63,13 +66,36 @@ export type GameEvent =
     | { MinigameReady: { minigame_type: string } }
     | { MinigameStarted: { minigame_type: string } }
     | { MinigameEnded: { result: MinigameResult } }
+    | "MinigameSkipped"
     | { TurnEnded: { next_player: string } }
-    | { GameEnded: { winner_id: string; final_coins: number } }
+    | { GameEnded: { winners: string[]; final_coins: number; standings: Standing[] } }
     | { GameInitialized: { random_seed: number } }
+    | { SeedRevealed: { player_id: string } }
+    | { SeedFinalized: { revealed: string[]; missing: string[] } }
     | { PlayerRegistered: { name: string; player_id: string } }
     | { GameStarted: { player_count: number } }
     | { BetPlaced: { player_id: string; amount: number } }
-    | { WheelSpun: { round: number; outcome: number } };
+    | { WheelSpun: { round: number; outcome: WheelOutcome } }
+    | { PlayersSwappedCoins: { swaps: Array<[string, string]> } }
+    | { CoinsStolen: { thief: string; victim: string; amount: number } }
+    | { JackpotWon: { player_id: string; amount: number } }
+    | { JackpotChanged: { amount: number; total: number } };
+
+export type WheelOutcome =
+    | "Nothing"
+    | "RandomPayout"
+    | "AllOrNothing"
+    | "Minigame"
+    | "SwapCoins"
+    | "StealFromLeader"
+    | "Jackpot";
+
+export interface Standing {
+    player_id: string;
+    coins: number;
+    stars: number;
+    rank: number;
+}
 
 export type GameStateCommand =
     | {
@@ -95,15 +121,34 @@ export type GameStateEvent =
           payload: { result: MinigameResult };
       };
 
+export interface GameConfig {
+    rounds: number;
+    max_players: number;
+    registration_window_ms: number;
+    betting_window_ms: number;
+    spin_timeout_ms: number;
+    no_bet_penalty: number;
+    unrevealed_seed_penalty: number;
+    max_deposit: number;
+    wheel: { slices: Array<[WheelOutcome, number]> };
+    steal_amount: number;
+    payout_policy: "Proportional" | "WinnerTakesAll" | { TopSplit: number[] };
+    rake_bps: number;
+    treasury: string | null;
+}
+
 export interface GameState {
     players: Player[];
     phase: GamePhase;
-    max_players: number;
+    config: GameConfig;
     minigames: string[];
     dice: { min: number; max: number; seed: number };
     round_started_at: number;
     round: number;
     bets: Record<string, number>;
+    pot: number;
+    jackpot: number;
+    seed_commitments: Record<string, { commitment: number[]; secret: number[] | null }>;
     backend_identity: string;
     last_interaction_time: number;
     lane_id: string;
@@ -229,6 +274,14 @@ class BoardGameService extends BaseWebSocketService {
     }
 
     async registerPlayer(name: string, deposit: number) {
+        // Commit to a random secret, revealed once registration is over to seed the dice.
+        const secret = crypto.getRandomValues(new Uint8Array(32));
+        const identity = new TextEncoder().encode(getLocalPlayerId());
+        const preimage = new Uint8Array(secret.length + identity.length);
+        preimage.set(secret);
+        preimage.set(identity, secret.length);
+        const commitment = new Uint8Array(await crypto.subtle.digest("SHA-256", preimage));
+        localStorage.setItem(`seed_secret_${getLocalPlayerId()}`, JSON.stringify(Array.from(secret)));
         await this.send(
             {
                 type: "GameState",
@@ -239,6 +292,7 @@ class BoardGameService extends BaseWebSocketService {
                             RegisterPlayer: {
                                 name,
                                 deposit,
+                                seed_commitment: Array.from(commitment),
                             },
                         },
                     },
@@ -248,6 +302,12 @@ class BoardGameService extends BaseWebSocketService {
         );
     }
 
+    async revealSeed() {
+        const secret = localStorage.getItem(`seed_secret_${getLocalPlayerId()}`);
+        if (!secret) return;
+        await this.sendAction({ RevealSeed: { secret: JSON.parse(secret) } });
+    }
+
     async startGame() {
         await this.send(
             {